@@ -5,7 +5,7 @@ use std::process::exit;
 use std::io::{stdin, stdout, Write};
 use rhai::{Engine, RegisterFn, Scope};
 
-fn showit<T: Display>(x: &mut T) -> () {
+fn showit<T: Display>(x: &mut T) {
     println!("{}", x)
 }
 
@@ -35,8 +35,13 @@ pub fn main() {
             println!("input error: {}", e);
         }
 
-        if let Err(e) = engine.consume_with_scope(&mut scope, &input) {
-            println!("error: {}", e);
+        match engine.eval_line(&mut scope, &input) {
+            // Echo the value of expressions, using the registered `print`
+            Ok(Some(mut value)) => {
+                let _ = engine.call_fn_raw("print".to_string(), vec![value.as_mut()]);
+            }
+            Ok(None) => (),
+            Err(e) => println!("error: {}", e),
         }
     }
 }
@@ -4,7 +4,7 @@ use std::fmt::Display;
 extern crate rhai;
 use rhai::{Engine, RegisterFn};
 
-fn showit<T: Display>(x: &mut T) -> () {
+fn showit<T: Display>(x: &mut T) {
     println!("{}", x)
 }
 
@@ -6,6 +6,9 @@ pub trait Any: StdAny {
 
     fn box_clone(&self) -> Box<Any>;
 
+    /// The Rust name of the type, for debugging
+    fn type_name(&self) -> &'static str;
+
     /// This type may only be implemented by `rhai`.
     #[doc(hidden)]
     fn _closed(&self) -> _Private;
@@ -25,6 +28,11 @@ impl<T> Any for T
         Box::new(self.clone())
     }
 
+    #[inline]
+    fn type_name(&self) -> &'static str {
+        ::std::any::type_name::<T>()
+    }
+
     fn _closed(&self) -> _Private { _Private }
 }
 
@@ -73,7 +81,7 @@ impl Clone for Box<Any> {
 
 impl fmt::Debug for Any {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.pad("Any")
+        write!(f, "Any({})", self.type_name())
     }
 }
 
@@ -0,0 +1,128 @@
+//! Helper module which defines `EngineBuilder`
+//! to assemble a customized `Engine`.
+
+use std::sync::Arc;
+
+use engine::{Engine, MAX_ARRAY_SIZE, MAX_STRING_SIZE};
+
+/// Configures and creates an `Engine`.
+///
+/// ```rust
+/// use rhai::{EngineBuilder, EvalAltResult};
+///
+/// let mut engine = EngineBuilder::new()
+///     .max_operations(1000)
+///     .on_print(|s| println!("script says: {}", s))
+///     .build();
+///
+/// assert_eq!(engine.eval::<()>("loop { }"), Err(EvalAltResult::ErrorTooManyOperations));
+/// ```
+pub struct EngineBuilder {
+    default_lib: bool,
+    sandboxed: bool,
+    strict_types: bool,
+    max_operations: Option<u64>,
+    max_call_depth: usize,
+    max_array_size: usize,
+    max_string_size: usize,
+    on_print: Option<Arc<Fn(&str)>>,
+}
+
+impl EngineBuilder {
+    /// Start from the same configuration as `Engine::new`
+    pub fn new() -> EngineBuilder {
+        EngineBuilder {
+            default_lib: true,
+            sandboxed: false,
+            strict_types: false,
+            max_operations: None,
+            max_call_depth: 64,
+            max_array_size: MAX_ARRAY_SIZE,
+            max_string_size: MAX_STRING_SIZE,
+            on_print: None,
+        }
+    }
+
+    /// Forget the functions a script defines once it finishes running,
+    /// so one script can't leave definitions behind for the next.
+    pub fn sandboxed(mut self) -> EngineBuilder {
+        self.sandboxed = true;
+        self
+    }
+
+    /// Make assigning a value of a different type to an existing variable
+    /// an `ErrorTypeMismatch`, so `let x = 5; x = "hi";` fails. Declaring
+    /// it again with `let` is still allowed.
+    pub fn strict_types(mut self) -> EngineBuilder {
+        self.strict_types = true;
+        self
+    }
+
+    /// Stop a run with `ErrorTooManyOperations` after it has executed
+    /// this many operations. Each statement, function call and loop
+    /// iteration is one operation, so a single statement which calls many
+    /// functions can't get around the limit.
+    pub fn max_operations(mut self, max: u64) -> EngineBuilder {
+        self.max_operations = Some(max);
+        self
+    }
+
+    /// Stop a run with `ErrorStackOverflow` once script functions have
+    /// called each other this many times without returning.
+    pub fn max_call_depth(mut self, depth: usize) -> EngineBuilder {
+        self.max_call_depth = depth;
+        self
+    }
+
+    /// Make `fill` fail with an error rather than build an array of more
+    /// than `size` elements. The default is 16,777,216.
+    pub fn max_array_size(mut self, size: usize) -> EngineBuilder {
+        self.max_array_size = size;
+        self
+    }
+
+    /// Make `repeat` and string `*` fail with an error rather than make a
+    /// string longer than `size` bytes. The default is 16,777,216.
+    pub fn max_string_size(mut self, size: usize) -> EngineBuilder {
+        self.max_string_size = size;
+        self
+    }
+
+    /// Don't register the default library of operators and types.
+    pub fn without_default_lib(mut self) -> EngineBuilder {
+        self.default_lib = false;
+        self
+    }
+
+    /// Register a `print` function, which hands the printed text to `f`.
+    /// Values of custom types are shown using `Engine::register_display`.
+    pub fn on_print<F: Fn(&str) + 'static>(mut self, f: F) -> EngineBuilder {
+        self.on_print = Some(Arc::new(f));
+        self
+    }
+
+    /// Create the configured engine
+    pub fn build(self) -> Engine {
+        let mut engine = Engine::new_raw();
+
+        if self.default_lib {
+            Engine::register_default_lib(&mut engine);
+        }
+
+        engine.sandboxed = self.sandboxed;
+        engine.strict_types = self.strict_types;
+        engine.max_operations = self.max_operations;
+        engine.max_call_depth = self.max_call_depth;
+        engine.max_array_size = self.max_array_size;
+        engine.max_string_size = self.max_string_size;
+
+        if let Some(sink) = self.on_print {
+            engine.register_fn_for_any("print", move |e, x| {
+                sink(&e.format_value(x, false));
+                Ok(Box::new(()))
+            });
+        }
+
+        engine
+    }
+}
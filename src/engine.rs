@@ -1,40 +1,91 @@
 use std::any::TypeId;
 use std::borrow::Borrow;
+use std::cell::Cell;
 use std::cmp::{PartialEq, PartialOrd};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt;
+use std::mem;
 use std::sync::Arc;
-use std::ops::{Add, BitAnd, BitOr, BitXor, Deref, Div, Mul, Neg, Rem, Shl, Shr, Sub};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Deref, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub};
 
 use any::{Any, AnyExt};
+use builder::EngineBuilder;
 use fn_register::{Mut, RegisterFn};
-use parser::{lex, parse, Expr, FnDef, Stmt};
+use parser::{lex, lint, parse, Expr, FnDef, LintWarning, ParseError, Position, Stmt, AST};
 use call::FunArgs;
 
 #[derive(Debug)]
 pub enum EvalAltResult {
-    ErrorFunctionNotFound(String),
+    ErrorParsing(ParseError),
+    ErrorFunctionNotFound(String, Position),
     ErrorFunctionArgMismatch,
-    ErrorFunctionCallNotSupported,
-    ErrorIndexMismatch,
+    /// The index and the length of the array
+    ErrorArrayBounds(i64, usize),
+    ErrorIndexingType(String),
+    ErrorIndexExpr(String),
     ErrorIfGuardMismatch,
-    ErrorVariableNotFound(String),
-    ErrorFunctionArityNotSupported,
+    ErrorVariableNotFound(String, Position),
     ErrorAssignmentToUnknownLHS,
+    ErrorAssignmentToConstant(String),
+    ErrorTypeMismatch(String),
     ErrorMismatchOutputType(String),
     ErrorCantOpenScriptFile,
+    ErrorArithmetic(String),
+    ErrorRuntimePanic(String),
+    ErrorRuntime(String),
+    ErrorTooManyOperations,
+    ErrorStackOverflow,
     InternalErrorMalformedDotExpression,
-    LoopBreak,
+    LoopBreak(Box<Any>),
+    LoopContinue,
     Return(Box<Any>),
 }
 
 impl EvalAltResult {
+    /// Where in the script the error happened, if that is known
+    pub fn position(&self) -> Option<Position> {
+        match *self {
+            EvalAltResult::ErrorVariableNotFound(_, pos)
+            | EvalAltResult::ErrorFunctionNotFound(_, pos) if !pos.is_none() => Some(pos),
+            EvalAltResult::ErrorParsing(ref e) => e.position(),
+            _ => None,
+        }
+    }
+
+    /// Give an error the position of the expression it came out of, unless
+    /// it already has a position from deeper inside, like a function body
+    fn at(self, pos: Position) -> EvalAltResult {
+        match self {
+            EvalAltResult::ErrorVariableNotFound(s, ref p) if p.is_none() => EvalAltResult::ErrorVariableNotFound(s, pos),
+            EvalAltResult::ErrorFunctionNotFound(s, ref p) if p.is_none() => EvalAltResult::ErrorFunctionNotFound(s, pos),
+            other => other,
+        }
+    }
+
+    /// Like the `Display` output, but if the position of the error is known,
+    /// followed by the line of `src` it happened on with a caret under the
+    /// spot. `src` should be the script that produced the error.
+    pub fn display_with_source(&self, src: &str) -> String {
+        match self.position().and_then(|pos| pos.snippet(src)) {
+            Some(snippet) => format!("{}\n{}", self, snippet),
+            None => self.to_string(),
+        }
+    }
+
     fn as_str(&self) -> Option<&str> {
         match *self {
-            EvalAltResult::ErrorVariableNotFound(ref s) => Some(s.as_str()),
-            EvalAltResult::ErrorFunctionNotFound(ref s) => Some(s.as_str()),
+            EvalAltResult::ErrorParsing(ref p) => Some(p.description()),
+            EvalAltResult::ErrorVariableNotFound(ref s, _) => Some(s.as_str()),
+            EvalAltResult::ErrorFunctionNotFound(ref s, _) => Some(s.as_str()),
             EvalAltResult::ErrorMismatchOutputType(ref s) => Some(s.as_str()),
+            EvalAltResult::ErrorAssignmentToConstant(ref s) => Some(s.as_str()),
+            EvalAltResult::ErrorTypeMismatch(ref s) => Some(s.as_str()),
+            EvalAltResult::ErrorArithmetic(ref s) => Some(s.as_str()),
+            EvalAltResult::ErrorRuntimePanic(ref s) => Some(s.as_str()),
+            EvalAltResult::ErrorRuntime(ref s) => Some(s.as_str()),
+            EvalAltResult::ErrorIndexingType(ref s) => Some(s.as_str()),
+            EvalAltResult::ErrorIndexExpr(ref s) => Some(s.as_str()),
             _ => None
         }
     }
@@ -45,18 +96,27 @@ impl PartialEq for EvalAltResult {
         use EvalAltResult::*;
 
         match (self, other) {
-            (&ErrorFunctionNotFound(ref a), &ErrorFunctionNotFound(ref b)) => a == b,
+            (&ErrorParsing(ref a), &ErrorParsing(ref b)) => a == b,
+            (&ErrorFunctionNotFound(ref a, ref pa), &ErrorFunctionNotFound(ref b, ref pb)) => a == b && pa == pb,
             (&ErrorFunctionArgMismatch, &ErrorFunctionArgMismatch) => true,
-            (&ErrorFunctionCallNotSupported, &ErrorFunctionCallNotSupported) => true,
-            (&ErrorIndexMismatch, &ErrorIndexMismatch) => true,
+            (&ErrorArrayBounds(a, la), &ErrorArrayBounds(b, lb)) => a == b && la == lb,
+            (&ErrorIndexingType(ref a), &ErrorIndexingType(ref b)) => a == b,
+            (&ErrorIndexExpr(ref a), &ErrorIndexExpr(ref b)) => a == b,
             (&ErrorIfGuardMismatch, &ErrorIfGuardMismatch) => true,
-            (&ErrorVariableNotFound(ref a), &ErrorVariableNotFound(ref b)) => a == b,
-            (&ErrorFunctionArityNotSupported, &ErrorFunctionArityNotSupported) => true,
+            (&ErrorVariableNotFound(ref a, ref pa), &ErrorVariableNotFound(ref b, ref pb)) => a == b && pa == pb,
             (&ErrorAssignmentToUnknownLHS, &ErrorAssignmentToUnknownLHS) => true,
+            (&ErrorAssignmentToConstant(ref a), &ErrorAssignmentToConstant(ref b)) => a == b,
+            (&ErrorTypeMismatch(ref a), &ErrorTypeMismatch(ref b)) => a == b,
             (&ErrorMismatchOutputType(ref a), &ErrorMismatchOutputType(ref b)) => a == b,
             (&ErrorCantOpenScriptFile, &ErrorCantOpenScriptFile) => true,
+            (&ErrorArithmetic(ref a), &ErrorArithmetic(ref b)) => a == b,
+            (&ErrorRuntimePanic(ref a), &ErrorRuntimePanic(ref b)) => a == b,
+            (&ErrorRuntime(ref a), &ErrorRuntime(ref b)) => a == b,
+            (&ErrorTooManyOperations, &ErrorTooManyOperations) => true,
+            (&ErrorStackOverflow, &ErrorStackOverflow) => true,
             (&InternalErrorMalformedDotExpression, &InternalErrorMalformedDotExpression) => true,
-            (&LoopBreak, &LoopBreak) => true,
+            (&LoopBreak(_), &LoopBreak(_)) => true,
+            (&LoopContinue, &LoopContinue) => true,
             _ => false,
         }
     }
@@ -65,26 +125,31 @@ impl PartialEq for EvalAltResult {
 impl Error for EvalAltResult {
     fn description(&self) -> &str {
         match *self {
-            EvalAltResult::ErrorFunctionNotFound(_) => "Function not found",
+            EvalAltResult::ErrorParsing(_) => "Syntax error",
+            EvalAltResult::ErrorFunctionNotFound(_, _) => "Function not found",
             EvalAltResult::ErrorFunctionArgMismatch => "Function argument types do not match",
-            EvalAltResult::ErrorFunctionCallNotSupported => {
-                "Function call with > 2 argument not supported"
-            }
-            EvalAltResult::ErrorIndexMismatch => "Index does not match array",
+            EvalAltResult::ErrorArrayBounds(_, _) => "Array index out of bounds",
+            EvalAltResult::ErrorIndexingType(_) => "Cannot index value of type",
+            EvalAltResult::ErrorIndexExpr(_) => "Index must be an integer, got",
             EvalAltResult::ErrorIfGuardMismatch => "If guards expect boolean expression",
-            EvalAltResult::ErrorVariableNotFound(_) => "Variable not found",
-            EvalAltResult::ErrorFunctionArityNotSupported => {
-                "Functions of more than 3 parameters are not yet supported"
-            }
+            EvalAltResult::ErrorVariableNotFound(_, _) => "Variable not found",
             EvalAltResult::ErrorAssignmentToUnknownLHS => {
                 "Assignment to an unsupported left-hand side"
             }
+            EvalAltResult::ErrorAssignmentToConstant(_) => "Assignment to a constant",
+            EvalAltResult::ErrorTypeMismatch(_) => "Assignment would change the type of a variable",
             EvalAltResult::ErrorMismatchOutputType(_) => "Cast of output failed",
             EvalAltResult::ErrorCantOpenScriptFile => "Cannot open script file",
+            EvalAltResult::ErrorArithmetic(_) => "Arithmetic error",
+            EvalAltResult::ErrorRuntimePanic(_) => "Registered function panicked",
+            EvalAltResult::ErrorRuntime(_) => "Runtime error",
+            EvalAltResult::ErrorTooManyOperations => "Script exceeded the maximum number of operations",
+            EvalAltResult::ErrorStackOverflow => "Script functions called each other more than max_call_depth deep",
             EvalAltResult::InternalErrorMalformedDotExpression => {
                 "[Internal error] Unexpected expression in dot expression"
             }
-            EvalAltResult::LoopBreak => "Loop broken before completion (not an error)",
+            EvalAltResult::LoopBreak(_) => "Loop broken before completion (not an error)",
+            EvalAltResult::LoopContinue => "Loop continued with the next pass (not an error)",
             EvalAltResult::Return(_) => "Function returned value (not an error)",
         }
     }
@@ -96,8 +161,12 @@ impl Error for EvalAltResult {
 
 impl fmt::Display for EvalAltResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(s) = self.as_str() {
+        if let (Some(s), Some(pos)) = (self.as_str(), self.position()) {
+            write!(f, "{} at {}: {}", self.description(), pos, s)
+        } else if let Some(s) = self.as_str() {
             write!(f, "{}: {}", self.description(), s)
+        } else if let EvalAltResult::ErrorArrayBounds(i, len) = *self {
+            write!(f, "{}: index {} of an array of length {}", self.description(), i, len)
         } else {
             write!(f, "{}", self.description())
         }
@@ -110,6 +179,12 @@ pub struct FnSpec {
     args: Option<Vec<TypeId>>,
 }
 
+impl FnSpec {
+    pub(crate) fn new(ident: &str, args: Vec<TypeId>) -> FnSpec {
+        FnSpec { ident: ident.to_owned(), args: Some(args) }
+    }
+}
+
 /// Rhai's engine type. This is what you use to run Rhai scripts
 ///
 /// ```rust
@@ -129,15 +204,62 @@ pub struct Engine {
     /// A hashmap containing all functions known to the engine
     pub fns: HashMap<FnSpec, Arc<FnIntExt>>,
     pub type_names: HashMap<TypeId,String>,
+    /// Read-only values visible to every script run by the engine
+    pub constants: HashMap<String, Box<Any>>,
+    /// Whether functions defined by a script are forgotten once it finishes
+    pub sandboxed: bool,
+    /// The maximum number of operations a single run may execute: statements,
+    /// function calls and loop iterations, plus one for each copy `repeat` makes
+    pub max_operations: Option<u64>,
+    /// Whether assigning a value of another type to a variable is an error
+    pub strict_types: bool,
+    /// How deeply script functions can call each other, so that runaway
+    /// recursion fails with `ErrorStackOverflow` instead of crashing
+    pub max_call_depth: usize,
+    /// The most elements `fill` will put in one array
+    pub max_array_size: usize,
+    /// The longest string, in bytes, `repeat` and string `*` will make
+    pub max_string_size: usize,
+    /// How to display values of custom types, see `register_display`
+    pub formatters: HashMap<TypeId, Arc<Fn(&Any) -> String>>,
+    /// How to copy values of custom types, see `register_type_with_clone`
+    pub cloners: HashMap<TypeId, Arc<Fn(&Any) -> Box<Any>>>,
+    /// Called for functions which aren't registered, see `on_unknown_fn`
+    pub unknown_fn: Option<Arc<FnResolver>>,
+    /// Builtins like `print` which take values of any type, kept apart from
+    /// `fns` so that a script function of the same name can't replace them
+    fns_for_any: HashMap<String, Arc<FnIntExt>>,
+    /// Functions registered from Rust which take their first argument by
+    /// value or by `&`, so calling one as a method can't change a property
+    pub(crate) read_only_fns: HashSet<FnSpec>,
+    operations: Cell<u64>,
+    call_depth: Cell<usize>,
+    /// The state of the generator behind `rand`, see `seed_rng`
+    #[cfg(feature = "rand")]
+    pub(crate) rng: Cell<u64>,
 }
 
 pub enum FnIntExt {
     Ext(Box<FnAny>),
+    ExtWithEngine(Box<FnWithEngine>),
+    ExtWithScope(Box<FnWithScope>),
     Int(FnDef),
 }
 
 pub type FnAny = Fn(Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult>;
 
+/// A native function which needs to call back into the engine, e.g. to
+/// dispatch operators on values whose type is only known at runtime.
+pub type FnWithEngine = Fn(&Engine, Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult>;
+
+/// A native function which reads or changes the variables of the script
+/// calling it, see `Engine::register_fn_with_scope`.
+pub type FnWithScope = Fn(&mut Scope, Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult>;
+
+/// A host function which resolves calls to functions the engine doesn't know,
+/// see `Engine::on_unknown_fn`.
+pub type FnResolver = Fn(&str, &mut [&mut Any]) -> Option<Result<Box<Any>, EvalAltResult>>;
+
 /// A type containing information about current scope.
 /// Useful for keeping state between `Engine` runs
 ///
@@ -154,6 +276,181 @@ pub type FnAny = Fn(Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult>;
 /// Between runs, `Engine` only remembers functions when not using own `Scope`.
 pub type Scope = Vec<(String, Box<Any>)>;
 
+/// Helpers for hosts that seed a scope or manage block structure themselves.
+///
+/// ```rust
+/// use rhai::{Engine, Scope, ScopeExt};
+///
+/// let mut engine = Engine::new();
+/// let mut my_scope = Scope::new();
+///
+/// let frame = my_scope.push_frame();
+/// engine.eval_with_scope::<()>(&mut my_scope, "let x = 5;").unwrap();
+/// my_scope.pop_frame(frame);
+///
+/// assert!(engine.eval_with_scope::<i64>(&mut my_scope, "x").is_err());
+/// ```
+pub trait ScopeExt {
+    /// Start a new frame. Bindings added after this call remain visible
+    /// alongside the outer ones until the frame is popped.
+    fn push_frame(&mut self) -> usize;
+
+    /// Remove every binding added since the matching `push_frame`.
+    fn pop_frame(&mut self, frame: usize);
+
+    /// Add a variable, boxing the value for you.
+    fn push_value<T: Any>(&mut self, name: &str, value: T);
+
+    /// Add every variable of `other` after the ones already here. Variables
+    /// are looked up from the most recent, so where both scopes have the
+    /// same name, the one from `other` wins.
+    fn merge(&mut self, other: Scope);
+
+    /// A copy of the value of the most recent variable called `name`, or
+    /// `None` if there isn't one or it doesn't hold a `T`.
+    fn get_value<T: Any + Clone>(&self, name: &str) -> Option<T>;
+
+    /// Overwrite the most recent variable called `name`, even with a value of
+    /// another type, or add it if there isn't one.
+    fn set_value<T: Any>(&mut self, name: &str, value: T);
+}
+
+impl ScopeExt for Scope {
+    fn push_frame(&mut self) -> usize {
+        self.len()
+    }
+
+    fn pop_frame(&mut self, frame: usize) {
+        self.truncate(frame);
+    }
+
+    fn push_value<T: Any>(&mut self, name: &str, value: T) {
+        self.push((name.to_owned(), Box::new(value)));
+    }
+
+    fn merge(&mut self, other: Scope) {
+        self.extend(other);
+    }
+
+    fn get_value<T: Any + Clone>(&self, name: &str) -> Option<T> {
+        self.iter()
+            .rev()
+            .find(|&&(ref id, _)| id == name)
+            .and_then(|&(_, ref value)| value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    fn set_value<T: Any>(&mut self, name: &str, value: T) {
+        match self.iter_mut().rev().find(|&&mut (ref id, _)| id == name) {
+            Some(&mut (_, ref mut old)) => *old = Box::new(value),
+            None => self.push_value(name, value),
+        }
+    }
+}
+
+/// `_` can be used in place of a variable or parameter name to
+/// discard the value instead of binding it.
+fn is_throwaway(name: &str) -> bool {
+    name == "_"
+}
+
+/// Returns `idx` as a position in `arr`, or `None` if it is negative or past
+/// the end of the array. Arrays never grow by indexing.
+fn array_index(arr: &[Box<Any>], idx: i64) -> Option<usize> {
+    if idx >= 0 && (idx as u64) < arr.len() as u64 {
+        Some(idx as usize)
+    } else {
+        None
+    }
+}
+
+/// A `break` or `continue` which got to the top of a script wasn't in any
+/// loop, so it's an error rather than something for the host to handle
+fn outside_loop(e: EvalAltResult) -> EvalAltResult {
+    match e {
+        EvalAltResult::LoopBreak(_) => EvalAltResult::ErrorRuntime("Cannot break outside of a loop".into()),
+        EvalAltResult::LoopContinue => EvalAltResult::ErrorRuntime("Cannot continue outside of a loop".into()),
+        e => e,
+    }
+}
+
+/// The default for `max_array_size`, so that a script asking for a huge
+/// array gets an error rather than running the host out of memory
+pub(crate) const MAX_ARRAY_SIZE: usize = 1 << 24;
+
+/// The default for `max_string_size`, for the same reason
+pub(crate) const MAX_STRING_SIZE: usize = 1 << 24;
+
+/// The operators which can have an in-place version, registered as `op=`,
+/// for `x = x op y` and the compound assignment `x op= y`
+const COMPOUND_OPS: [&str; 10] = ["+", "-", "*", "/", "%", "&", "|", "^", "<<", ">>"];
+
+/// Store a value taken out of the scope back into the binding it came from.
+/// The binding is found by position, not by name, so a variable of the same
+/// name declared while the value was out can't receive it instead.
+fn write_back(scope: &mut Scope, sc_idx: usize, target: Box<Any>) {
+    if let Some(&mut (_, ref mut val)) = scope.get_mut(sc_idx) {
+        *val = target;
+    }
+}
+
+/// Like `write_back`, for an element of an array. If evaluating the rest of
+/// the expression replaced the array, or shrunk it, the element is dropped.
+fn write_back_element(scope: &mut Scope, sc_idx: usize, idx: usize, target: Box<Any>) {
+    let slot = scope
+        .get_mut(sc_idx)
+        .and_then(|&mut (_, ref mut val)| val.downcast_mut::<Vec<Box<Any>>>())
+        .and_then(|arr| arr.get_mut(idx));
+
+    if let Some(slot) = slot {
+        *slot = target;
+    }
+}
+
+/// `register_fn` boxes whatever a function returns, so a function which already
+/// returns a `Box<Any>` ends up with a box in a box. Unwrap it, so that scripts
+/// and `eval` see the value itself.
+fn unbox_dynamic(mut value: Box<Any>) -> Box<Any> {
+    while value.is::<Box<Any>>() {
+        value = *value.downcast::<Box<Any>>().unwrap();
+    }
+
+    value
+}
+
+/// Runs a registered native function. With the `catch_panics` feature, a panic
+/// inside it is turned into `ErrorRuntimePanic` instead of unwinding into the host.
+#[cfg(feature = "catch_panics")]
+fn catch_panic<F>(f: F) -> Result<Box<Any>, EvalAltResult>
+where
+    F: FnOnce() -> Result<Box<Any>, EvalAltResult>,
+{
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    // Registered functions only see the arguments they were handed, which are
+    // discarded together with the rest of the call if it panics.
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let msg = if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "<unknown panic>".to_string()
+        };
+
+        Err(EvalAltResult::ErrorRuntimePanic(msg))
+    })
+}
+
+#[cfg(not(feature = "catch_panics"))]
+#[inline]
+fn catch_panic<F>(f: F) -> Result<Box<Any>, EvalAltResult>
+where
+    F: FnOnce() -> Result<Box<Any>, EvalAltResult>,
+{
+    f()
+}
+
 impl Engine {
     pub fn call_fn<'a, I, A, T>(&self, ident: I, args: A) -> Result<T, EvalAltResult>
     where
@@ -171,10 +468,24 @@ impl Engine {
 
     /// Universal method for calling functions, that are either
     /// registered with the `Engine` or written in Rhai
+    ///
+    /// Overloads are looked up by the exact types of `args`, so which one is
+    /// called never depends on the order they were registered in.
     pub fn call_fn_raw(
         &self,
         ident: String,
         args: Vec<&mut Any>,
+    ) -> Result<Box<Any>, EvalAltResult> {
+        self.call_fn_in_scope(&mut Scope::new(), ident, args)
+    }
+
+    // Like `call_fn_raw`, for calls made by a script, so that functions
+    // registered with `register_fn_with_scope` can see its variables.
+    fn call_fn_in_scope(
+        &self,
+        scope: &mut Scope,
+        ident: String,
+        args: Vec<&mut Any>,
     ) -> Result<Box<Any>, EvalAltResult> {
         debug_println!(
             "Trying to call function {:?} with args {:?}",
@@ -187,33 +498,134 @@ impl Engine {
             args: Some(args.iter().map(|a| <Any as Any>::type_id(&**a)).collect()),
         };
 
-        self.fns
-            .get(&spec)
-            .or_else(|| {
-                let spec1 = FnSpec { ident: ident.clone(), args: None };
-                self.fns.get(&spec1)
-            })
-            .ok_or_else(|| {
-                let typenames = args.iter().map(|x| self.nice_type_name((&**x).box_clone())).collect::<Vec<_>>();
-                EvalAltResult::ErrorFunctionNotFound(format!("{} ({})", ident, typenames.join(",")))
-            })
-            .and_then(move |f| match **f {
-                FnIntExt::Ext(ref f) => f(args),
-                FnIntExt::Int(ref f) => {
-                    let mut scope = Scope::new();
-                    scope.extend(
-                        f.params
-                            .iter()
-                            .cloned()
-                            .zip(args.iter().map(|x| (&**x).box_clone())),
-                    );
-
-                    match self.eval_stmt(&mut scope, &*f.body) {
-                        Err(EvalAltResult::Return(x)) => Ok(x),
-                        other => other,
-                    }
+        self.count_operations(1)?;
+
+        let f = match self.fns.get(&spec).or_else(|| {
+            let spec1 = FnSpec { ident: ident.clone(), args: None };
+            self.fns.get(&spec1)
+        }).or_else(|| self.fns_for_any.get(&ident)) {
+            Some(f) => f,
+            None => return self.call_unknown_fn(&ident, args),
+        };
+
+        match **f {
+            FnIntExt::Ext(ref f) => catch_panic(|| f(args)).map(unbox_dynamic),
+            FnIntExt::ExtWithEngine(ref f) => catch_panic(|| f(self, args)).map(unbox_dynamic),
+            FnIntExt::ExtWithScope(ref f) => catch_panic(|| f(scope, args)).map(unbox_dynamic),
+            FnIntExt::Int(ref f) => {
+                if self.call_depth.get() >= self.max_call_depth {
+                    return Err(EvalAltResult::ErrorStackOverflow);
                 }
-            })
+
+                let mut scope = Scope::new();
+                scope.extend(
+                    f.params
+                        .iter()
+                        .cloned()
+                        .zip(args.iter().map(|x| self.copy_value(&**x)))
+                        .filter(|&(ref name, _)| !is_throwaway(name)),
+                );
+
+                self.call_depth.set(self.call_depth.get() + 1);
+                let result = self.eval_stmt(&mut scope, &*f.body);
+                self.call_depth.set(self.call_depth.get() - 1);
+
+                match result {
+                    Err(EvalAltResult::Return(x)) => Ok(x),
+                    // Otherwise it would break out of a loop in the caller
+                    Err(EvalAltResult::LoopBreak(_)) => Err(EvalAltResult::ErrorRuntime(
+                        format!("Cannot break out of function {}, only out of a loop", f.name))),
+                    Err(EvalAltResult::LoopContinue) => Err(EvalAltResult::ErrorRuntime(
+                        format!("Cannot continue out of function {}, only in a loop", f.name))),
+                    other => other,
+                }
+            }
+        }
+    }
+
+    /// Describe which function a call to `name` with arguments of the given
+    /// types would run, or `None` if the call would fail. Types are named the
+    /// way error messages name them, such as `integer` or a name given to
+    /// `register_type_name`. Useful for finding out why an overload isn't
+    /// being picked.
+    ///
+    /// ```rust
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// assert_eq!(engine.explain_call("+", &["integer", "integer"]),
+    ///            Some("+ (integer, integer), registered for these argument types".to_string()));
+    /// assert_eq!(engine.explain_call("+", &["integer", "boolean"]), None);
+    /// ```
+    pub fn explain_call(&self, name: &str, arg_type_names: &[&str]) -> Option<String> {
+        let args = arg_type_names
+            .iter()
+            .map(|n| self.type_names.iter().find(|&(_, v)| v == n).map(|(&t, _)| t))
+            .collect::<Option<Vec<_>>>();
+
+        if let Some(args) = args {
+            if self.fns.contains_key(&FnSpec { ident: name.to_owned(), args: Some(args) }) {
+                return Some(format!(
+                    "{} ({}), registered for these argument types",
+                    name,
+                    arg_type_names.join(", ")
+                ));
+            }
+        }
+
+        match self.fns.get(&FnSpec { ident: name.to_owned(), args: None }).map(|f| &**f) {
+            Some(&FnIntExt::Int(ref f)) => {
+                Some(format!("{}({}), defined by a script", name, f.params.join(", ")))
+            }
+            Some(_) => Some(format!("{}, registered for arguments of any type", name)),
+            None if self.fns_for_any.contains_key(name) => {
+                Some(format!("{}, registered for arguments of any type", name))
+            }
+            None if self.unknown_fn.is_some() => {
+                Some(format!("{}, left to the on_unknown_fn resolver", name))
+            }
+            None => None,
+        }
+    }
+
+    // Nothing is registered under `ident` for these arguments, so give the
+    // `on_unknown_fn` resolver a chance before reporting it.
+    fn call_unknown_fn(&self, ident: &str, mut args: Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult> {
+        if let Some(ref resolver) = self.unknown_fn {
+            if let Some(result) = resolver(ident, &mut args) {
+                return result.map(unbox_dynamic);
+            }
+        }
+
+        let typenames = args.iter().map(|x| self.nice_type_name((&**x).box_clone())).collect::<Vec<_>>();
+        Err(EvalAltResult::ErrorFunctionNotFound(format!("{} ({})", ident, typenames.join(",")), Position::none()))
+    }
+
+    /// Set a function to call when a script calls a function which isn't
+    /// registered for the given arguments, instead of failing straight away
+    /// with `ErrorFunctionNotFound`. The resolver gets the function name and
+    /// the arguments, and returns `None` if it doesn't know the function either.
+    ///
+    /// ```rust
+    /// use rhai::{Any, Engine};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.on_unknown_fn(|name, args| if name == "answer" && args.is_empty() {
+    ///     Some(Ok(Box::new(42i64) as Box<Any>))
+    /// } else {
+    ///     None
+    /// });
+    ///
+    /// assert_eq!(engine.eval::<i64>("answer()"), Ok(42));
+    /// assert!(engine.eval::<i64>("question()").is_err());
+    /// ```
+    pub fn on_unknown_fn<F>(&mut self, f: F)
+    where
+        F: 'static + Fn(&str, &mut [&mut Any]) -> Option<Result<Box<Any>, EvalAltResult>>,
+    {
+        self.unknown_fn = Some(Arc::new(f));
     }
 
     pub fn register_fn_raw(&mut self, ident: String, args: Option<Vec<TypeId>>, f: Box<FnAny>) {
@@ -221,15 +633,154 @@ impl Engine {
 
         let spec = FnSpec { ident, args };
 
+        self.read_only_fns.remove(&spec);
         self.fns.insert(spec, Arc::new(FnIntExt::Ext(f)));
     }
 
+    /// Like `register_fn_raw`, for a builtin which needs the engine, such as
+    /// to call other functions. `args` of `None` matches any arguments.
+    pub(crate) fn register_fn_with_engine(&mut self, ident: &str, args: Option<Vec<TypeId>>, f: Box<FnWithEngine>) {
+        debug_println!("Register; {:?} with args {:?}", ident, args);
+
+        let spec = FnSpec { ident: ident.to_owned(), args };
+
+        self.fns.insert(spec, Arc::new(FnIntExt::ExtWithEngine(f)));
+    }
+
+    /// Register a function which can read and change the variables of the
+    /// script calling it, for reflection-style functions like listing or
+    /// setting variables by name. This is an advanced feature: the function
+    /// sees every variable in scope, including those of enclosing blocks, and
+    /// must check its own arguments, whose types are given in `args` as for
+    /// `register_fn_raw`. Called from a script function, it sees that
+    /// function's variables. Called from the host, as with `call_fn`, the
+    /// scope is empty.
+    ///
+    /// ```rust
+    /// use rhai::{Engine, Scope};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn_with_scope("var_count", Some(vec![]), |scope: &mut Scope, _| {
+    ///     Ok(Box::new(scope.len() as i64))
+    /// });
+    ///
+    /// assert_eq!(engine.eval::<i64>("let a = 1; let b = 2; var_count()"), Ok(2));
+    /// ```
+    pub fn register_fn_with_scope<F>(&mut self, ident: &str, args: Option<Vec<TypeId>>, f: F)
+    where
+        F: 'static + Fn(&mut Scope, Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult>,
+    {
+        let spec = FnSpec { ident: ident.to_owned(), args };
+
+        self.fns.insert(spec, Arc::new(FnIntExt::ExtWithScope(Box::new(f))));
+    }
+
+    /// Make every function currently registered as `existing` callable as `alias`
+    /// too, sharing the same implementation. Overloads registered under
+    /// `existing` afterwards are not picked up by the alias.
+    pub fn register_fn_alias(&mut self, existing: &str, alias: &str) {
+        let aliased: Vec<_> = self.fns
+            .iter()
+            .filter(|&(spec, _)| spec.ident == existing)
+            .map(|(spec, f)| {
+                let spec = FnSpec { ident: alias.to_string(), args: spec.args.clone() };
+                (spec, f.clone())
+            })
+            .collect();
+
+        self.fns.extend(aliased);
+    }
+
+    /// Register a named constant, which scripts can read like a variable but not
+    /// assign to. Variables in scope shadow constants with the same name.
+    pub fn register_constant<T: Any + Clone>(&mut self, name: &str, value: T) {
+        self.constants.insert(name.to_owned(), Box::new(value));
+    }
+
+    /// Whether a function called `name` taking `arity` arguments is known,
+    /// either registered or defined by a script this engine has run. Functions
+    /// which accept values of any type, like `to_string`, check their
+    /// arguments only when called, so they count as known at every arity.
+    /// Functions only an `on_unknown_fn` resolver provides are not known.
+    ///
+    /// ```rust
+    /// use rhai::Engine;
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// assert!(engine.has_fn("+", 2));
+    /// assert!(!engine.has_fn("+", 3));
+    /// assert!(!engine.has_fn("frobnicate", 1));
+    /// ```
+    pub fn has_fn(&self, name: &str, arity: usize) -> bool {
+        if arity == 1 && self.fns_for_any.contains_key(name) {
+            return true;
+        }
+
+        self.fns.iter().any(|(spec, f)| {
+            spec.ident == name
+                && match (&spec.args, &**f) {
+                    (&Some(ref args), _) => args.len() == arity,
+                    (&None, &FnIntExt::Int(ref f)) => f.params.len() == arity,
+                    (&None, _) => true,
+                }
+        })
+    }
+
     /// Register a type for use with Engine. Keep in mind that
     /// your type must implement Clone.
     pub fn register_type<T: Any>(&mut self) {
         // currently a no-op, exists for future extensibility
     }
 
+    /// Register a type along with how to copy its values, for when `Clone`
+    /// isn't what scripts should see. Values are copied whenever a variable,
+    /// constant or array element is read, including the elements of a copied
+    /// array, the loop variable of `for` and the arguments of a script
+    /// function, so for a handle type backed by an `Rc` this decides between
+    /// copying the handle and copying what it points to. Values passed by value to registered functions are still
+    /// copied with `Clone`. Scripts can make copies explicitly with `clone`.
+    pub fn register_type_with_clone<T: Any + Clone, F>(&mut self, clone_fn: F)
+    where
+        F: 'static + Fn(&T) -> T,
+    {
+        self.register_type::<T>();
+
+        let clone_fn = Arc::new(clone_fn);
+
+        let f = clone_fn.clone();
+        self.cloners.insert(TypeId::of::<T>(), Arc::new(move |x: &Any| {
+            Box::new(f(x.downcast_ref::<T>().unwrap())) as Box<Any>
+        }));
+
+        self.register_fn("clone", move |x: &mut T| clone_fn(x));
+        self.read_only_fns.insert(FnSpec::new("clone", vec![TypeId::of::<T>()]));
+    }
+
+    /// Copy a value the way reading a variable does. Arrays and maps are
+    /// copied element by element, so handles inside them are copied the same
+    /// way as handles on their own.
+    fn copy_value(&self, value: &Any) -> Box<Any> {
+        if self.cloners.is_empty() {
+            return value.box_clone();
+        }
+
+        if let Some(arr) = value.downcast_ref::<Vec<Box<Any>>>() {
+            return Box::new(arr.iter().map(|x| self.copy_value(x.as_ref())).collect::<Vec<_>>());
+        }
+        if let Some(map) = value.downcast_ref::<HashMap<String, Box<Any>>>() {
+            return Box::new(map.iter()
+                .map(|(k, v)| (k.clone(), self.copy_value(v.as_ref())))
+                .collect::<HashMap<_, _>>());
+        }
+
+        match self.cloners.get(&<Any as Any>::type_id(value)) {
+            Some(clone_fn) => clone_fn(value),
+            None => value.box_clone(),
+        }
+    }
+
     /// Register a type, providing a name for nice error messages.
     pub fn register_type_name<T: Any>(&mut self, name: &str) {
         self.register_type::<T>();
@@ -237,6 +788,156 @@ impl Engine {
         self.type_names.insert(TypeId::of::<T>(), name.into());
     }
 
+    /// Register how values of a custom type are displayed by `print`,
+    /// `to_string` and `debug`, and make them concatenable with strings.
+    /// Types without one are displayed as their type name in angle brackets.
+    pub fn register_display<T: Any + Clone, F>(&mut self, f: F)
+    where
+        F: 'static + Fn(&T) -> String,
+    {
+        let f = Arc::new(f);
+
+        let format = f.clone();
+        self.formatters.insert(TypeId::of::<T>(), Arc::new(move |x: &Any| {
+            x.downcast_ref::<T>().map(|x| format(x)).unwrap_or_default()
+        }));
+
+        let format = f.clone();
+        self.register_fn("+", move |s: String, x: T| s + &format(&x));
+        self.register_fn("+", move |x: &mut T, s: String| f(x) + &s);
+    }
+
+    /// Register `+`, `-`, `*` and `/` between two values of a custom numeric
+    /// type, the way they are registered for the built-in ones.
+    ///
+    /// ```rust
+    /// use rhai::{Engine, RegisterFn};
+    ///
+    /// #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    /// struct Fixed(i64);
+    ///
+    /// impl std::ops::Add for Fixed {
+    ///     type Output = Fixed;
+    ///     fn add(self, rhs: Fixed) -> Fixed { Fixed(self.0 + rhs.0) }
+    /// }
+    /// # impl std::ops::Sub for Fixed {
+    /// #     type Output = Fixed;
+    /// #     fn sub(self, rhs: Fixed) -> Fixed { Fixed(self.0 - rhs.0) }
+    /// # }
+    /// # impl std::ops::Mul for Fixed {
+    /// #     type Output = Fixed;
+    /// #     fn mul(self, rhs: Fixed) -> Fixed { Fixed(self.0 * rhs.0 / 100) }
+    /// # }
+    /// # impl std::ops::Div for Fixed {
+    /// #     type Output = Fixed;
+    /// #     fn div(self, rhs: Fixed) -> Fixed { Fixed(self.0 * 100 / rhs.0) }
+    /// # }
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_arithmetic::<Fixed>();
+    /// engine.register_comparison::<Fixed>();
+    /// engine.register_fn("fixed", Fixed);
+    ///
+    /// assert_eq!(engine.eval::<Fixed>("fixed(150) + fixed(25)"), Ok(Fixed(175)));
+    /// assert_eq!(engine.eval::<bool>("fixed(150) > fixed(25)"), Ok(true));
+    /// ```
+    pub fn register_arithmetic<T>(&mut self)
+    where
+        T: Any + Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+    {
+        self.register_fn("+", |x: T, y: T| x + y);
+        self.register_fn("-", |x: T, y: T| x - y);
+        self.register_fn("*", |x: T, y: T| x * y);
+        self.register_fn("/", |x: T, y: T| x / y);
+    }
+
+    /// Register `<`, `<=`, `>`, `>=`, `==` and `!=` between two values of a
+    /// custom type. See `register_arithmetic`.
+    pub fn register_comparison<T: Any + Clone + PartialOrd>(&mut self) {
+        self.register_fn("<", |x: T, y: T| x < y);
+        self.register_fn("<=", |x: T, y: T| x <= y);
+        self.register_fn(">", |x: T, y: T| x > y);
+        self.register_fn(">=", |x: T, y: T| x >= y);
+        self.register_fn("==", |x: T, y: T| x == y);
+        self.register_fn("!=", |x: T, y: T| x != y);
+    }
+
+    /// Register unary `-` for a custom numeric type. See `register_arithmetic`.
+    pub fn register_negation<T: Any + Clone + Neg<Output = T>>(&mut self) {
+        self.register_fn("-", |x: T| -x);
+    }
+
+    /// Turn any value into text, the way `to_string` does in scripts.
+    /// With `debug`, strings and chars are shown quoted.
+    pub fn format_value(&self, value: &Any, debug: bool) -> String {
+        if let Some(format) = self.formatters.get(&<Any as Any>::type_id(value)) {
+            return format(value);
+        }
+
+        macro_rules! format_builtin {
+            ($( $y:ty ),*) => (
+                $(
+                    if let Some(x) = value.downcast_ref::<$y>() {
+                        return x.to_string();
+                    }
+                )*
+            )
+        }
+
+        format_builtin!(i32, i64, u32, u64, bool);
+
+        // Floats are shown the shortest way that reads back as the same value,
+        // always with a `.` or an exponent so they can't be taken for integers
+        if let Some(x) = value.downcast_ref::<f64>() {
+            return format!("{:?}", x);
+        }
+
+        if let Some(x) = value.downcast_ref::<f32>() {
+            return format!("{:?}", x);
+        }
+
+        if let Some(x) = value.downcast_ref::<String>() {
+            return if debug { format!("{:?}", x) } else { x.clone() };
+        }
+
+        if let Some(x) = value.downcast_ref::<char>() {
+            return if debug { format!("{:?}", x) } else { x.to_string() };
+        }
+
+        if value.is::<()>() {
+            return "()".to_string();
+        }
+
+        if let Some(arr) = value.downcast_ref::<Vec<Box<Any>>>() {
+            let items: Vec<String> = arr.iter().map(|x| self.format_value(x.as_ref(), true)).collect();
+            return format!("[{}]", items.join(", "));
+        }
+
+        match self.type_names.get(&<Any as Any>::type_id(value)) {
+            Some(name) => format!("<{}>", name),
+            None => "<unknown>".to_string(),
+        }
+    }
+
+    /// Register a function of one argument which accepts values of any type.
+    /// It's only used if there is no overload for the exact argument type.
+    /// A script function of the same name is used instead while it's
+    /// defined, but doesn't replace this one, which is used again once the
+    /// script function is forgotten.
+    pub(crate) fn register_fn_for_any<F>(&mut self, ident: &str, f: F)
+    where
+        F: 'static + Fn(&Engine, &mut Any) -> Result<Box<Any>, EvalAltResult>,
+    {
+        self.fns_for_any.insert(ident.to_owned(), Arc::new(FnIntExt::ExtWithEngine(Box::new(move |engine, mut args| {
+            if args.len() != 1 {
+                return Err(EvalAltResult::ErrorFunctionArgMismatch);
+            }
+
+            f(engine, args.pop().unwrap())
+        }))));
+    }
+
     /// Register a get function for a member of a registered type
     pub fn register_get<T: Clone + Any, U: Clone + Any, F>(&mut self, name: &str, get_fn: F)
     where
@@ -255,6 +956,15 @@ impl Engine {
         self.register_fn(&set_name, set_fn);
     }
 
+    /// Register how to read `x[i]` when `x` is of a custom collection type,
+    /// including when `x` comes from a getter, as in `obj.items[i]`
+    pub fn register_index_get<T: Clone + Any, I: Clone + Any, U: Clone + Any, F>(&mut self, index_fn: F)
+    where
+        F: 'static + Fn(&mut T, I) -> U,
+    {
+        self.register_fn("index$get", index_fn);
+    }
+
     /// Shorthand for registering both getters and setters
     pub fn register_get_set<T: Clone + Any, U: Clone + Any, F, G>(
         &mut self,
@@ -275,50 +985,136 @@ impl Engine {
         this_ptr: &mut Any,
         dot_rhs: &Expr,
     ) -> Result<Box<Any>, EvalAltResult> {
+        self.get_dot_val_changed(scope, this_ptr, dot_rhs).map(|(value, _)| value)
+    }
+
+    /// Like `get_dot_val_helper`, also telling whether the chain may have
+    /// changed `this_ptr`, by calling a method on it which takes it by `&mut`
+    fn get_dot_val_changed(
+        &self,
+        scope: &mut Scope,
+        this_ptr: &mut Any,
+        dot_rhs: &Expr,
+    ) -> Result<(Box<Any>, bool), EvalAltResult> {
         use std::iter::once;
 
         match *dot_rhs {
-            Expr::FnCall(ref fn_name, ref args) => {
+            Expr::FnCall(ref fn_name, ref args, pos) => {
                 let mut args: Vec<Box<Any>> = args.iter()
                     .map(|arg| self.eval_expr(scope, arg))
                     .collect::<Result<Vec<_>, _>>()?;
-                let args = once(this_ptr)
+                let args: Vec<&mut Any> = once(this_ptr)
                     .chain(args.iter_mut().map(|b| b.as_mut()))
                     .collect();
+                let changed = self.may_change_first_arg(fn_name, &args);
 
-                self.call_fn_raw(fn_name.to_owned(), args)
+                self.call_fn_in_scope(scope, fn_name.to_owned(), args)
+                    .map(|value| (value, changed))
+                    .map_err(|e| e.at(pos))
             }
-            Expr::Identifier(ref id) => {
+            Expr::Identifier(ref id, pos) => {
                 let get_fn_name = "get$".to_string() + id;
 
-                self.call_fn_raw(get_fn_name, vec![this_ptr])
+                self.call_fn_raw(get_fn_name, vec![this_ptr]).map(|value| (value, false)).map_err(|e| e.at(pos))
             }
-            Expr::Index(ref id, ref idx_raw) => {
-                let idx = self.eval_expr(scope, idx_raw)?;
+            Expr::Index(ref id, ref idx_raw, pos) => {
+                let mut idx = self.eval_expr(scope, idx_raw)?;
                 let get_fn_name = "get$".to_string() + id;
 
-                let mut val = self.call_fn_raw(get_fn_name, vec![this_ptr])?;
+                let mut val = self.call_fn_raw(get_fn_name, vec![this_ptr]).map_err(|e| e.at(pos))?;
 
-                ((*val).downcast_mut() as Option<&mut Vec<Box<Any>>>)
-                    .and_then(|arr| idx.downcast_ref::<i64>().map(|idx| (arr, *idx as usize)))
-                    .map(|(arr, idx)| arr[idx].clone())
-                    .ok_or(EvalAltResult::ErrorIndexMismatch)
+                self.index_value(val.as_mut(), idx.as_mut()).map(|value| (value, false))
             }
+            // A getter hands out a copy, so a method which changed it, as in
+            // `obj.list.push(1)` or `obj.list[0].push(1)`, has to have its
+            // change given to the setter
             Expr::Dot(ref inner_lhs, ref inner_rhs) => match **inner_lhs {
-                Expr::Identifier(ref id) => {
+                Expr::Index(ref id, ref idx_raw, pos) => {
+                    let mut idx = self.eval_expr(scope, idx_raw)?;
                     let get_fn_name = "get$".to_string() + id;
-                    self.call_fn_raw(get_fn_name, vec![this_ptr])
-                        .and_then(|mut v| self.get_dot_val_helper(scope, v.as_mut(), inner_rhs))
+
+                    let mut list = self.call_fn_raw(get_fn_name, vec![this_ptr]).map_err(|e| e.at(pos))?;
+                    let mut v = self.index_value(list.as_mut(), idx.as_mut())?;
+                    let (value, changed) = self.get_dot_val_changed(scope, v.as_mut(), inner_rhs)?;
+
+                    if !changed {
+                        return Ok((value, false));
+                    }
+
+                    // Only arrays can be changed through an index, custom indexers are read-only
+                    match (list.downcast_mut::<Vec<Box<Any>>>(), idx.downcast_ref::<i64>()) {
+                        (Some(list), Some(&i)) => list[i as usize] = v,
+                        _ => return Ok((value, false)),
+                    }
+
+                    self.write_back_property(this_ptr, id, list)?;
+                    Ok((value, true))
+                }
+                _ => {
+                    // The left side of the chain may itself be a property or a
+                    // method call, so evaluate it first and continue on its result
+                    let (mut v, this_changed) = self.get_dot_val_changed(scope, this_ptr, inner_lhs)?;
+                    let (value, changed) = self.get_dot_val_changed(scope, v.as_mut(), inner_rhs)?;
+
+                    match **inner_lhs {
+                        Expr::Identifier(ref id, _) if changed => {
+                            self.write_back_property(this_ptr, id, v)?;
+                            Ok((value, true))
+                        }
+                        _ => Ok((value, this_changed)),
+                    }
                 }
-                _ => Err(EvalAltResult::InternalErrorMalformedDotExpression),
             },
             _ => Err(EvalAltResult::InternalErrorMalformedDotExpression),
         }
     }
 
+    /// Whether calling `fn_name` with `args` may change the first of them.
+    /// Script functions get copies of their arguments, and builtins which
+    /// need the engine only read theirs.
+    fn may_change_first_arg(&self, fn_name: &str, args: &[&mut Any]) -> bool {
+        let spec = FnSpec {
+            ident: fn_name.to_owned(),
+            args: Some(args.iter().map(|a| <Any as Any>::type_id(&**a)).collect()),
+        };
+        let f = self.fns.get(&spec).or_else(|| {
+            self.fns.get(&FnSpec { ident: fn_name.to_owned(), args: None })
+        }).or_else(|| self.fns_for_any.get(fn_name));
+
+        match f.map(|f| &**f) {
+            Some(&FnIntExt::Ext(_)) => !self.read_only_fns.contains(&spec),
+            Some(&FnIntExt::ExtWithEngine(_)) | Some(&FnIntExt::Int(_)) => false,
+            Some(&FnIntExt::ExtWithScope(_)) | None => true,
+        }
+    }
+
+    // Properties with only a getter are read-only, so changes to them are dropped
+    fn write_back_property(&self, this_ptr: &mut Any, id: &str, mut value: Box<Any>) -> Result<(), EvalAltResult> {
+        let set_fn_name = "set$".to_string() + id;
+        let spec = FnSpec {
+            ident: set_fn_name.clone(),
+            args: Some(vec![<Any as Any>::type_id(this_ptr), <Any as Any>::type_id(value.as_ref())]),
+        };
+
+        if !self.fns.contains_key(&spec) {
+            return Ok(());
+        }
+
+        self.call_fn_raw(set_fn_name, vec![this_ptr, value.as_mut()]).map(|_| ())
+    }
+
+    fn assignment_target_not_found(&self, id: &str, pos: Position) -> EvalAltResult {
+        if self.constants.contains_key(id) {
+            EvalAltResult::ErrorAssignmentToConstant(id.to_owned())
+        } else {
+            EvalAltResult::ErrorVariableNotFound(id.to_owned(), pos)
+        }
+    }
+
     fn search_scope<'a, F, T>(
         scope: &'a mut Scope,
         id: &str,
+        pos: Position,
         map: F,
     ) -> Result<(usize, T), EvalAltResult>
     where
@@ -329,7 +1125,7 @@ impl Engine {
             .enumerate()
             .rev()
             .find(|&(_, &mut (ref name, _))| *id == *name)
-            .ok_or_else(|| EvalAltResult::ErrorVariableNotFound(id.to_owned()))
+            .ok_or_else(|| EvalAltResult::ErrorVariableNotFound(id.to_owned(), pos))
             .and_then(move |(idx, &mut (_, ref mut val))| map(val.as_mut()).map(|val| (idx, val)))
     }
 
@@ -337,19 +1133,16 @@ impl Engine {
         &self,
         scope: &mut Scope,
         id: &str,
+        pos: Position,
         idx: &Expr,
     ) -> Result<(usize, usize, Box<Any>), EvalAltResult> {
-        let idx_boxed = self.eval_expr(scope, idx)?
-            .downcast::<i64>()
-            .map_err(|_| EvalAltResult::ErrorIndexMismatch)?;
-        let idx = *idx_boxed as usize;
-        let (idx_sc, val) = Self::search_scope(scope, id, |val| {
-            ((*val).downcast_mut() as Option<&mut Vec<Box<Any>>>)
-                .map(|arr| arr[idx].clone())
-                .ok_or(EvalAltResult::ErrorIndexMismatch)
+        let idx = self.eval_expr(scope, idx)?;
+        let (idx_sc, val) = Self::search_scope(scope, id, pos, |val| {
+            self.index_array(val, idx.as_ref()).map(|x| self.copy_value(x.as_ref()))
         })?;
 
-        Ok((idx_sc, idx, val))
+        // Indexing succeeded, so the index is known to be an in-range integer
+        Ok((idx_sc, *idx.downcast_ref::<i64>().unwrap() as usize, val))
     }
 
     fn get_dot_val(
@@ -359,23 +1152,30 @@ impl Engine {
         dot_rhs: &Expr,
     ) -> Result<Box<Any>, EvalAltResult> {
         match *dot_lhs {
-            Expr::Identifier(ref id) => {
-                let (sc_idx, mut target) = Self::search_scope(scope, id, |x| Ok(x.box_clone()))?;
+            Expr::Identifier(ref id, pos) => {
+                let (sc_idx, mut target) = match Self::search_scope(scope, id, pos, |x| Ok(x.box_clone())) {
+                    Ok(found) => found,
+                    Err(e) => {
+                        // Constants are read-only, so changes made through the dot are dropped
+                        let mut target = self.constants.get(id).map(|x| self.copy_value(x.as_ref())).ok_or(e)?;
+                        return self.get_dot_val_helper(scope, target.as_mut(), dot_rhs);
+                    }
+                };
                 let value = self.get_dot_val_helper(scope, target.as_mut(), dot_rhs);
 
                 // In case the expression mutated `target`, we need to reassign it because
                 // of the above `clone`.
-                scope[sc_idx].1 = target;
+                write_back(scope, sc_idx, target);
 
                 value
             }
-            Expr::Index(ref id, ref idx_raw) => {
-                let (sc_idx, idx, mut target) = self.array_value(scope, id, idx_raw)?;
+            Expr::Index(ref id, ref idx_raw, pos) => {
+                let (sc_idx, idx, mut target) = self.array_value(scope, id, pos, idx_raw)?;
                 let value = self.get_dot_val_helper(scope, target.as_mut(), dot_rhs);
 
                 // In case the expression mutated `target`, we need to reassign it because
                 // of the above `clone`.
-                scope[sc_idx].1.downcast_mut::<Vec<Box<Any>>>().unwrap()[idx] = target;
+                write_back_element(scope, sc_idx, idx, target);
 
                 value
             }
@@ -390,14 +1190,15 @@ impl Engine {
         mut source_val: Box<Any>,
     ) -> Result<Box<Any>, EvalAltResult> {
         match *dot_rhs {
-            Expr::Identifier(ref id) => {
+            Expr::Identifier(ref id, pos) => {
                 let set_fn_name = "set$".to_string() + id;
-                self.call_fn_raw(set_fn_name, vec![this_ptr, source_val.as_mut()])
+                self.call_fn_raw(set_fn_name, vec![this_ptr, source_val.as_mut()]).map_err(|e| e.at(pos))
             }
             Expr::Dot(ref inner_lhs, ref inner_rhs) => match **inner_lhs {
-                Expr::Identifier(ref id) => {
+                Expr::Identifier(ref id, pos) => {
                     let get_fn_name = "get$".to_string() + id;
                     self.call_fn_raw(get_fn_name, vec![this_ptr])
+                        .map_err(|e| e.at(pos))
                         .and_then(|mut v| {
                             self.set_dot_val_helper(v.as_mut(), inner_rhs, source_val)
                                 .map(|_| v) // Discard Ok return value
@@ -405,7 +1206,7 @@ impl Engine {
                         .and_then(|mut v| {
                             let set_fn_name = "set$".to_string() + id;
 
-                            self.call_fn_raw(set_fn_name, vec![this_ptr, v.as_mut()])
+                            self.call_fn_raw(set_fn_name, vec![this_ptr, v.as_mut()]).map_err(|e| e.at(pos))
                         })
                 }
                 _ => Err(EvalAltResult::InternalErrorMalformedDotExpression),
@@ -422,23 +1223,24 @@ impl Engine {
         source_val: Box<Any>,
     ) -> Result<Box<Any>, EvalAltResult> {
         match *dot_lhs {
-            Expr::Identifier(ref id) => {
-                let (sc_idx, mut target) = Self::search_scope(scope, id, |x| Ok(x.box_clone()))?;
+            Expr::Identifier(ref id, pos) => {
+                let (sc_idx, mut target) = Self::search_scope(scope, id, pos, |x| Ok(x.box_clone()))
+                    .map_err(|_| self.assignment_target_not_found(id, pos))?;
                 let value = self.set_dot_val_helper(target.as_mut(), dot_rhs, source_val);
 
                 // In case the expression mutated `target`, we need to reassign it because
                 // of the above `clone`.
-                scope[sc_idx].1 = target;
+                write_back(scope, sc_idx, target);
 
                 value
             }
-            Expr::Index(ref id, ref idx_raw) => {
-                let (sc_idx, idx, mut target) = self.array_value(scope, id, idx_raw)?;
+            Expr::Index(ref id, ref idx_raw, pos) => {
+                let (sc_idx, idx, mut target) = self.array_value(scope, id, pos, idx_raw)?;
                 let value = self.set_dot_val_helper(target.as_mut(), dot_rhs, source_val);
 
                 // In case the expression mutated `target`, we need to reassign it because
                 // of the above `clone`.
-                scope[sc_idx].1.downcast_mut::<Vec<Box<Any>>>().unwrap()[idx] = target;
+                write_back_element(scope, sc_idx, idx, target);
 
                 value
             }
@@ -446,61 +1248,193 @@ impl Engine {
         }
     }
 
-    fn eval_expr(&self, scope: &mut Scope, expr: &Expr) -> Result<Box<Any>, EvalAltResult> {
-        match *expr {
-            Expr::IntConst(i) => Ok(Box::new(i)),
-            Expr::FloatConst(i) => Ok(Box::new(i)),
-            Expr::StringConst(ref s) => Ok(Box::new(s.clone())),
-            Expr::CharConst(ref c) => Ok(Box::new(*c)),
-            Expr::Identifier(ref id) => {
-                for &mut (ref name, ref mut val) in &mut scope.iter_mut().rev() {
-                    if *id == *name {
-                        return Ok(val.clone());
-                    }
+    /// Handles assignments of the form `x = x op y`, which is also what `x op= y`
+    /// desugars to, for the operators in `COMPOUND_OPS`. If an in-place `op=`
+    /// function is registered for the operand types, it is called on the
+    /// variable directly instead of building a new value and replacing the old
+    /// one. Returns `None` for any other assignment.
+    fn eval_update(
+        &self,
+        scope: &mut Scope,
+        id: &str,
+        rhs: &Expr,
+    ) -> Option<Result<Box<Any>, EvalAltResult>> {
+        let (op, arg, pos, var_pos) = match *rhs {
+            Expr::FnCall(ref op, ref args, pos) if args.len() == 2 && COMPOUND_OPS.contains(&op.as_str()) => {
+                match args[0] {
+                    Expr::Identifier(ref lhs, var_pos) if lhs == id => (op, &args[1], pos, var_pos),
+                    _ => return None,
                 }
-                Err(EvalAltResult::ErrorVariableNotFound(id.clone()))
-            }
-            Expr::Index(ref id, ref idx_raw) => {
-                self.array_value(scope, id, idx_raw).map(|(_, _, x)| x)
             }
-            Expr::Assignment(ref id, ref rhs) => {
-                let rhs_val = self.eval_expr(scope, rhs)?;
+            _ => return None,
+        };
 
-                match **id {
-                    Expr::Identifier(ref n) => {
-                        for &mut (ref name, ref mut val) in &mut scope.iter_mut().rev() {
-                            if *n == *name {
-                                *val = rhs_val;
+        // The variable is read before the other operand is evaluated, so that
+        // operand mustn't be able to change it
+        if self.may_change_var(arg, id) {
+            return None;
+        }
 
-                                return Ok(Box::new(()));
+        let (sc_idx, _) = match Self::search_scope(scope, id, var_pos, |_| Ok(())) {
+            Ok(found) => found,
+            Err(_) => return Some(Err(self.assignment_target_not_found(id, var_pos))),
+        };
+
+        let mut arg = match self.eval_expr(scope, arg) {
+            Ok(arg) => arg,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let in_place = FnSpec {
+            ident: op.to_string() + "=",
+            args: Some(vec![<Any as Any>::type_id(&*scope[sc_idx].1), <Any as Any>::type_id(&*arg)]),
+        };
+
+        // An in-place function changes its target through `&mut`, so the
+        // variable keeps its type without a strict types check
+        if self.fns.contains_key(&in_place) {
+            let target = scope[sc_idx].1.as_mut();
+
+            return Some(
+                self.call_fn_raw(in_place.ident, vec![target, arg.as_mut()])
+                    .map(|_| self.copy_value(scope[sc_idx].1.as_ref()))
+                    .map_err(|e| e.at(pos)),
+            );
+        }
+
+        let mut current = self.copy_value(scope[sc_idx].1.as_ref());
+
+        Some(
+            self.call_fn_raw(op.to_owned(), vec![current.as_mut(), arg.as_mut()])
+                .map_err(|e| e.at(pos))
+                .and_then(|val| {
+                    self.check_same_type(id, scope[sc_idx].1.as_ref(), val.as_ref())?;
+                    scope[sc_idx].1 = val;
+                    Ok(self.copy_value(scope[sc_idx].1.as_ref()))
+                }),
+        )
+    }
+
+    /// Whether evaluating `expr` might change the variable `id`, by naming it
+    /// or by calling a function which can see the caller's scope
+    fn may_change_var(&self, expr: &Expr, id: &str) -> bool {
+        match *expr {
+            Expr::Identifier(ref name, _) => name == id,
+            Expr::Index(ref name, ref idx, _) => name == id || self.may_change_var(idx, id),
+            Expr::FnCall(ref name, ref args, _) => {
+                self.fns.iter().any(|(spec, f)| match **f {
+                    FnIntExt::ExtWithScope(_) => spec.ident == *name,
+                    _ => false,
+                }) || args.iter().any(|arg| self.may_change_var(arg, id))
+            }
+            Expr::Assignment(ref lhs, ref rhs) | Expr::Dot(ref lhs, ref rhs) => {
+                self.may_change_var(lhs, id) || self.may_change_var(rhs, id)
+            }
+            Expr::Ternary(ref guard, ref then, ref otherwise) => {
+                self.may_change_var(guard, id) || self.may_change_var(then, id) || self.may_change_var(otherwise, id)
+            }
+            Expr::Array(ref items) | Expr::Sequence(ref items) => items.iter().any(|e| self.may_change_var(e, id)),
+            Expr::Block(_) => true,
+            Expr::IntConst(_) | Expr::FloatConst(_) | Expr::CharConst(_) | Expr::StringConst(_) | Expr::True
+            | Expr::False | Expr::Unit => false,
+        }
+    }
+
+    // A chain like `1 + 2 + 3 + ...` parses as calls nested in their first
+    // argument, one per operator. Walking down that argument in a loop rather
+    // than recursing lets a long chain run without overflowing the stack.
+    fn eval_call_chain(&self, scope: &mut Scope, expr: &Expr) -> Result<Box<Any>, EvalAltResult> {
+        let mut calls = Vec::new();
+        let mut first = expr;
+
+        while let Expr::FnCall(ref fn_name, ref args, pos) = *first {
+            calls.push((fn_name, args, pos));
+
+            match args.first() {
+                Some(arg) => first = arg,
+                None => break,
+            }
+        }
+
+        // A call without arguments ends the chain
+        let mut value = match *first {
+            Expr::FnCall(..) => None,
+            _ => Some(self.eval_expr(scope, first)?),
+        };
+
+        for &(fn_name, args, pos) in calls.iter().rev() {
+            let mut arg_vals = value.into_iter().collect::<Vec<_>>();
+
+            for arg in args.iter().skip(1) {
+                arg_vals.push(self.eval_expr(scope, arg)?);
+            }
+
+            value = Some(self.call_fn_in_scope(scope, fn_name.to_owned(), arg_vals.iter_mut().map(|b| b.as_mut()).collect())
+                .map_err(|e| e.at(pos))?);
+        }
+
+        Ok(value.unwrap())
+    }
+
+    fn eval_expr(&self, scope: &mut Scope, expr: &Expr) -> Result<Box<Any>, EvalAltResult> {
+        match *expr {
+            Expr::IntConst(i) => Ok(Box::new(i)),
+            Expr::FloatConst(i) => Ok(Box::new(i)),
+            Expr::StringConst(ref s) => Ok(Box::new(s.clone())),
+            Expr::CharConst(ref c) => Ok(Box::new(*c)),
+            Expr::Identifier(ref id, pos) => {
+                for &mut (ref name, ref mut val) in &mut scope.iter_mut().rev() {
+                    if *id == *name {
+                        return Ok(self.copy_value(val.as_ref()));
+                    }
+                }
+
+                self.constants
+                    .get(id)
+                    .map(|x| self.copy_value(x.as_ref()))
+                    .ok_or_else(|| EvalAltResult::ErrorVariableNotFound(id.clone(), pos))
+            }
+            Expr::Index(ref id, ref idx_raw, pos) => {
+                let mut idx = self.eval_expr(scope, idx_raw)?;
+
+                Self::search_scope(scope, id, pos, |val| self.index_value(val, idx.as_mut())).map(|(_, x)| x)
+            }
+            Expr::Assignment(ref id, ref rhs) => {
+                if let Expr::Identifier(ref n, _) = **id {
+                    if let Some(result) = self.eval_update(scope, n, rhs) {
+                        return result;
+                    }
+                }
+
+                let rhs_val = self.eval_expr(scope, rhs)?;
+                let assigned = self.copy_value(rhs_val.as_ref());
+
+                match **id {
+                    Expr::Identifier(ref n, pos) => {
+                        for &mut (ref name, ref mut val) in &mut scope.iter_mut().rev() {
+                            if *n == *name {
+                                self.check_same_type(n, val.as_ref(), rhs_val.as_ref())?;
+                                *val = rhs_val;
+
+                                return Ok(assigned);
                             }
                         }
-                        Err(EvalAltResult::ErrorVariableNotFound(n.clone()))
+                        Err(self.assignment_target_not_found(n, pos))
                     }
-                    Expr::Index(ref id, ref idx_raw) => {
+                    Expr::Index(ref id, ref idx_raw, pos) => {
                         let idx = self.eval_expr(scope, idx_raw)?;
 
                         for &mut (ref name, ref mut val) in &mut scope.iter_mut().rev() {
                             if *id == *name {
-                                if let Some(i) = idx.downcast_ref::<i64>() {
-                                    if let Some(arr_typed) =
-                                        (*val).downcast_mut() as Option<&mut Vec<Box<Any>>>
-                                    {
-                                        arr_typed[*i as usize] = rhs_val;
-                                        return Ok(Box::new(()));
-                                    } else {
-                                        return Err(EvalAltResult::ErrorIndexMismatch);
-                                    }
-                                } else {
-                                    return Err(EvalAltResult::ErrorIndexMismatch);
-                                }
+                                *self.index_array(val.as_mut(), idx.as_ref())? = rhs_val;
+                                return Ok(assigned);
                             }
                         }
 
-                        Err(EvalAltResult::ErrorVariableNotFound(id.clone()))
+                        Err(self.assignment_target_not_found(id, pos))
                     }
                     Expr::Dot(ref dot_lhs, ref dot_rhs) => {
-                        self.set_dot_val(scope, dot_lhs, dot_rhs, rhs_val)
+                        self.set_dot_val(scope, dot_lhs, dot_rhs, rhs_val).map(|_| assigned)
                     }
                     _ => Err(EvalAltResult::ErrorAssignmentToUnknownLHS),
                 }
@@ -516,42 +1450,80 @@ impl Engine {
 
                 Ok(Box::new(arr))
             }
-            Expr::FnCall(ref fn_name, ref args) => self.call_fn_raw(
-                fn_name.to_owned(),
-                args.iter()
-                    .map(|ex| self.eval_expr(scope, ex))
-                    .collect::<Result<Vec<Box<Any>>, _>>()?
-                    .iter_mut()
-                    .map(|b| b.as_mut())
-                    .collect(),
-            ),
+            Expr::Block(ref b) => self.eval_block(scope, b),
+            Expr::Ternary(ref guard, ref then_expr, ref else_expr) => {
+                let guard = self.eval_expr(scope, guard)?;
+                match guard.downcast::<bool>() {
+                    Ok(g) => {
+                        if *g {
+                            self.eval_expr(scope, then_expr)
+                        } else {
+                            self.eval_expr(scope, else_expr)
+                        }
+                    }
+                    Err(_) => Err(EvalAltResult::ErrorIfGuardMismatch),
+                }
+            }
+            Expr::Sequence(ref exprs) => {
+                let mut last: Box<Any> = Box::new(());
+
+                for e in exprs {
+                    last = self.eval_expr(scope, e)?;
+                }
+
+                Ok(last)
+            }
+            Expr::FnCall(_, _, _) => self.eval_call_chain(scope, expr),
             Expr::True => Ok(Box::new(true)),
             Expr::False => Ok(Box::new(false)),
             Expr::Unit => Ok(Box::new(())),
         }
     }
 
-    fn eval_stmt(&self, scope: &mut Scope, stmt: &Stmt) -> Result<Box<Any>, EvalAltResult> {
-        match *stmt {
-            Stmt::Expr(ref e) => self.eval_expr(scope, e),
-            Stmt::Block(ref b) => {
-                let prev_len = scope.len();
-                let mut last_result: Result<Box<Any>, EvalAltResult> = Ok(Box::new(()));
-
-                for s in b.iter() {
-                    last_result = self.eval_stmt(scope, s);
-                    if let Err(x) = last_result {
-                        last_result = Err(x);
-                        break;
-                    }
-                }
+    // A block has the value of its last statement, or `()` if it's empty
+    fn eval_block(&self, scope: &mut Scope, block: &[Stmt]) -> Result<Box<Any>, EvalAltResult> {
+        let frame = scope.push_frame();
+        let mut last_result: Result<Box<Any>, EvalAltResult> = Ok(Box::new(()));
 
-                while scope.len() > prev_len {
-                    scope.pop();
-                }
+        for s in block.iter() {
+            last_result = self.eval_stmt(scope, s);
+            if let Err(x) = last_result {
+                last_result = Err(x);
+                break;
+            }
+        }
+
+        scope.pop_frame(frame);
+
+        last_result
+    }
+
+    // Statements, function calls and loop iterations each count as one
+    // operation, and builtins which do a lot of work at once count more
+    pub(crate) fn count_operations(&self, n: u64) -> Result<(), EvalAltResult> {
+        if let Some(max) = self.max_operations {
+            let operations = self.operations.get().saturating_add(n);
 
-                last_result
+            if operations > max {
+                return Err(EvalAltResult::ErrorTooManyOperations);
             }
+            self.operations.set(operations);
+        }
+
+        Ok(())
+    }
+
+    fn eval_stmt(&self, scope: &mut Scope, stmt: &Stmt) -> Result<Box<Any>, EvalAltResult> {
+        self.count_operations(1)?;
+
+        match *stmt {
+            // An assignment gives the assigned value so that `a = b = 0` works,
+            // but as a statement of its own it has no value
+            Stmt::Expr(ref e) => match **e {
+                Expr::Assignment(_, _) => self.eval_expr(scope, e).map(|_| Box::new(()) as Box<Any>),
+                _ => self.eval_expr(scope, e),
+            },
+            Stmt::Block(ref b) => self.eval_block(scope, b),
             Stmt::If(ref guard, ref body) => {
                 let guard_result = self.eval_expr(scope, guard)?;
                 match guard_result.downcast::<bool>() {
@@ -579,12 +1551,15 @@ impl Engine {
                 }
             }
             Stmt::While(ref guard, ref body) => loop {
+                self.count_operations(1)?;
+
                 let guard_result = self.eval_expr(scope, guard)?;
                 match guard_result.downcast::<bool>() {
                     Ok(g) => {
                         if *g {
                             match self.eval_stmt(scope, body) {
-                                Err(EvalAltResult::LoopBreak) => return Ok(Box::new(())),
+                                Err(EvalAltResult::LoopBreak(x)) => return Ok(x),
+                                Err(EvalAltResult::LoopContinue) => (),
                                 Err(x) => return Err(x),
                                 _ => (),
                             }
@@ -596,24 +1571,98 @@ impl Engine {
                 }
             },
             Stmt::Loop(ref body) => loop {
+                self.count_operations(1)?;
+
                 match self.eval_stmt(scope, body) {
-                    Err(EvalAltResult::LoopBreak) => return Ok(Box::new(())),
+                    Err(EvalAltResult::LoopBreak(x)) => return Ok(x),
+                    Err(EvalAltResult::LoopContinue) => (),
                     Err(x) => return Err(x),
                     _ => (),
                 }
             },
-            Stmt::Break => Err(EvalAltResult::LoopBreak),
+            Stmt::ForRange(ref name, ref start, ref end, ref step, ref body) => {
+                let start = self.eval_expr(scope, start)?;
+                let end = self.eval_expr(scope, end)?;
+                let step = match *step {
+                    Some(ref step) => Some(self.eval_expr(scope, step)?),
+                    None => None,
+                };
+
+                for value in self.range_values(start, end, step)? {
+                    self.count_operations(1)?;
+
+                    let frame = scope.push_frame();
+                    if !is_throwaway(name) {
+                        scope.push((name.clone(), value));
+                    }
+
+                    let result = self.eval_stmt(scope, body);
+                    scope.pop_frame(frame);
+
+                    match result {
+                        Err(EvalAltResult::LoopBreak(x)) => return Ok(x),
+                        Err(EvalAltResult::LoopContinue) => (),
+                        Err(x) => return Err(x),
+                        _ => (),
+                    }
+                }
+
+                Ok(Box::new(()))
+            }
+            Stmt::For(ref name, ref array, ref body) => {
+                let array = self.eval_expr(scope, array)?;
+                let array = match array.downcast::<Vec<Box<Any>>>() {
+                    Ok(array) => array,
+                    Err(x) => return Err(EvalAltResult::ErrorRuntime(
+                        format!("Cannot loop over {}, only over an array or a range", self.nice_type_name(x)))),
+                };
+
+                for value in array.iter() {
+                    self.count_operations(1)?;
+
+                    let frame = scope.push_frame();
+                    if !is_throwaway(name) {
+                        scope.push((name.clone(), self.copy_value(value.as_ref())));
+                    }
+
+                    let result = self.eval_stmt(scope, body);
+                    scope.pop_frame(frame);
+
+                    match result {
+                        Err(EvalAltResult::LoopBreak(x)) => return Ok(x),
+                        Err(EvalAltResult::LoopContinue) => (),
+                        Err(x) => return Err(x),
+                        _ => (),
+                    }
+                }
+
+                Ok(Box::new(()))
+            }
+            Stmt::Break(None) => Err(EvalAltResult::LoopBreak(Box::new(()))),
+            Stmt::Continue => Err(EvalAltResult::LoopContinue),
+            Stmt::Break(Some(ref a)) => {
+                let result = self.eval_expr(scope, a)?;
+                Err(EvalAltResult::LoopBreak(result))
+            }
             Stmt::Return => Err(EvalAltResult::Return(Box::new(()))),
             Stmt::ReturnWithVal(ref a) => {
                 let result = self.eval_expr(scope, a)?;
                 Err(EvalAltResult::Return(result))
             }
-            Stmt::Var(ref name, ref init) => {
+            Stmt::Var(ref name, ref var_type, ref init) => {
                 match *init {
                     Some(ref v) => {
                         let i = self.eval_expr(scope, v)?;
-                        scope.push((name.clone(), i));
+
+                        if let Some(ref var_type) = *var_type {
+                            self.check_declared_type(name, var_type, i.as_ref())?;
+                        }
+
+                        if !is_throwaway(name) {
+                            scope.push((name.clone(), i));
+                        }
                     }
+                    None if is_throwaway(name) => (),
                     None => scope.push((name.clone(), Box::new(()))),
                 };
                 Ok(Box::new(()))
@@ -621,6 +1670,126 @@ impl Engine {
         }
     }
 
+    /// Looks up `val[idx]`, reporting which of the two was the wrong type if
+    /// `val` isn't an array or `idx` isn't an integer.
+    fn index_array<'a>(&self, val: &'a mut Any, idx: &Any) -> Result<&'a mut Box<Any>, EvalAltResult> {
+        let i = *idx.downcast_ref::<i64>()
+            .ok_or_else(|| EvalAltResult::ErrorIndexExpr(self.nice_type_name(idx.box_clone())))?;
+
+        if !val.is::<Vec<Box<Any>>>() {
+            return Err(EvalAltResult::ErrorIndexingType(self.nice_type_name(val.box_clone())));
+        }
+
+        let arr = val.downcast_mut::<Vec<Box<Any>>>().unwrap();
+
+        match array_index(arr, i) {
+            Some(i) => Ok(&mut arr[i]),
+            None => Err(EvalAltResult::ErrorArrayBounds(i, arr.len())),
+        }
+    }
+
+    // The values of `start..end step by`, going up from `start` if `by` is
+    // positive and down if it's negative, and stopping before `end`. Integer
+    // ranges give integers, and ranges with any float in them give floats.
+    fn range_values(
+        &self,
+        start: Box<Any>,
+        end: Box<Any>,
+        step: Option<Box<Any>>,
+    ) -> Result<Box<Iterator<Item = Box<Any>>>, EvalAltResult> {
+        let step = step.unwrap_or_else(|| Box::new(1i64));
+
+        if let (Some(&start), Some(&end), Some(&step)) =
+            (start.downcast_ref::<i64>(), end.downcast_ref::<i64>(), step.downcast_ref::<i64>())
+        {
+            if step == 0 {
+                return Err(EvalAltResult::ErrorArithmetic("Range step cannot be zero".into()));
+            }
+
+            let values = ::std::iter::successors(Some(start), move |x| x.checked_add(step))
+                .take_while(move |&x| if step > 0 { x < end } else { x > end })
+                .map(|x| Box::new(x) as Box<Any>);
+
+            return Ok(Box::new(values));
+        }
+
+        let as_float = |x: Box<Any>| {
+            if let Some(&i) = x.downcast_ref::<i64>() {
+                return Ok(i as f64);
+            }
+            if let Some(&f) = x.downcast_ref::<f64>() {
+                return Ok(f);
+            }
+
+            Err(EvalAltResult::ErrorRuntime(format!("Cannot loop over a range of {}", self.nice_type_name(x))))
+        };
+
+        let (start, end, step) = (as_float(start)?, as_float(end)?, as_float(step)?);
+
+        if step == 0.0 || step.is_nan() {
+            return Err(EvalAltResult::ErrorArithmetic("Range step cannot be zero".into()));
+        }
+
+        // Multiplying rather than adding up the steps keeps rounding errors
+        // from growing with every pass
+        let values = (0u64..)
+            .map(move |k| start + k as f64 * step)
+            .take_while(move |&x| if step > 0.0 { x < end } else { x > end })
+            .map(|x| Box::new(x) as Box<Any>);
+
+        Ok(Box::new(values))
+    }
+
+    // Reads `val[idx]`. Arrays are indexed directly, other types through the
+    // indexer registered for them with `register_index_get`, if there is one.
+    fn index_value(&self, val: &mut Any, idx: &mut Any) -> Result<Box<Any>, EvalAltResult> {
+        let spec = FnSpec {
+            ident: "index$get".to_string(),
+            args: Some(vec![<Any as Any>::type_id(val), <Any as Any>::type_id(idx)]),
+        };
+
+        if self.fns.contains_key(&spec) {
+            self.call_fn_raw(spec.ident, vec![val, idx])
+        } else {
+            self.index_array(val, idx).map(|x| self.copy_value(x.as_ref()))
+        }
+    }
+
+    // In strict mode a variable keeps the type of its first value. One declared
+    // without a value holds `()`, and can be given a value of any type once.
+    fn check_same_type(&self, name: &str, old: &Any, new: &Any) -> Result<(), EvalAltResult> {
+        let old_type = <Any as Any>::type_id(old);
+
+        if !self.strict_types || old_type == <Any as Any>::type_id(new) || old_type == TypeId::of::<()>() {
+            return Ok(());
+        }
+
+        Err(EvalAltResult::ErrorTypeMismatch(format!("{} holds {}, not {}", name,
+            self.nice_type_name(old.box_clone()), self.nice_type_name(new.box_clone()))))
+    }
+
+    // `let x: integer = ...` names the type the way `register_type_name` does,
+    // or with one of the Rust-like aliases for the builtin types
+    fn check_declared_type(&self, name: &str, var_type: &str, value: &Any) -> Result<(), EvalAltResult> {
+        let var_type = match var_type {
+            "int" | "i64" => "integer",
+            "f64" => "float",
+            "bool" => "boolean",
+            other => other,
+        };
+
+        if !self.type_names.values().any(|t| t == var_type) {
+            return Err(EvalAltResult::ErrorTypeMismatch(format!("{} is declared {}, which isn't a known type",
+                name, var_type)));
+        }
+
+        match self.type_names.get(&<Any as Any>::type_id(value)) {
+            Some(actual) if actual == var_type => Ok(()),
+            _ => Err(EvalAltResult::ErrorTypeMismatch(format!("{} is declared {}, not {}", name, var_type,
+                self.nice_type_name(value.box_clone())))),
+        }
+    }
+
     fn nice_type_name(&self, b: Box<Any>) -> String {
         let tid = <Any as Any>::type_id(&*b);
         if let Some(name) = self.type_names.get(&tid) {
@@ -631,6 +1800,7 @@ impl Engine {
     }
 
     /// Evaluate a file
+    #[cfg(feature = "fs")]
     pub fn eval_file<T: Any + Clone>(&mut self, fname: &str) -> Result<T, EvalAltResult> {
         use std::fs::File;
         use std::io::prelude::*;
@@ -655,54 +1825,231 @@ impl Engine {
         self.eval_with_scope(&mut scope, input)
     }
 
+    /// Evaluate a string which should give a `bool`
+    pub fn eval_bool(&mut self, input: &str) -> Result<bool, EvalAltResult> {
+        self.eval_expecting(input)
+    }
+
+    /// Evaluate a string which should give an integer
+    pub fn eval_int(&mut self, input: &str) -> Result<i64, EvalAltResult> {
+        self.eval_expecting(input)
+    }
+
+    /// Evaluate a string which should give a float
+    pub fn eval_float(&mut self, input: &str) -> Result<f64, EvalAltResult> {
+        self.eval_expecting(input)
+    }
+
+    /// Evaluate a string which should give a string
+    pub fn eval_string(&mut self, input: &str) -> Result<String, EvalAltResult> {
+        self.eval_expecting(input)
+    }
+
+    // Like `eval`, but a result of the wrong type names the type that was expected too
+    fn eval_expecting<T: Any + Clone>(&mut self, input: &str) -> Result<T, EvalAltResult> {
+        self.eval(input).map_err(|e| match e {
+            EvalAltResult::ErrorMismatchOutputType(actual) => {
+                let expected = self.type_names
+                    .get(&TypeId::of::<T>())
+                    .cloned()
+                    .unwrap_or_else(|| "<unknown>".to_string());
+
+                EvalAltResult::ErrorMismatchOutputType(format!("expected {}, got {}", expected, actual))
+            }
+            e => e,
+        })
+    }
+
     /// Evaluate with own scope
+    ///
+    /// Asking for `()` always succeeds, whatever the script's last expression
+    /// was, so `eval::<()>` can be used to run a script for its side effects.
+    ///
+    /// If the script fails part way through, the top-level variables it
+    /// declared before the failing statement stay in `scope`, so a REPL keeps
+    /// them. Variables declared inside blocks are always dropped.
     pub fn eval_with_scope<T: Any + Clone>(
         &mut self,
         scope: &mut Scope,
         input: &str,
     ) -> Result<T, EvalAltResult> {
-        let tokens = lex(input);
+        let ast = self.compile(input)?;
 
-        let mut peekables = tokens.peekable();
-        let tree = parse(&mut peekables);
+        self.eval_ast(scope, &ast)
+    }
 
-        match tree {
-            Ok((ref os, ref fns)) => {
-                let mut x: Result<Box<Any>, EvalAltResult> = Ok(Box::new(()));
+    /// Parse a script once, to be run as many times as needed with `eval_ast`.
+    ///
+    /// ```rust
+    /// use rhai::{Engine, Scope, ScopeExt};
+    ///
+    /// let mut engine = Engine::new();
+    /// let ast = engine.compile("fn double(x) { x * 2 } double(n)").unwrap();
+    ///
+    /// for n in 0..3 {
+    ///     let mut scope = Scope::new();
+    ///     scope.push_value("n", n as i64);
+    ///
+    ///     assert_eq!(engine.eval_ast::<i64>(&mut scope, &ast), Ok(n * 2));
+    /// }
+    /// ```
+    pub fn compile(&self, input: &str) -> Result<AST, EvalAltResult> {
+        let mut tokens = lex(input);
+        let (os, fns) = parse(&mut tokens).map_err(EvalAltResult::ErrorParsing)?;
+
+        Ok(AST(os, fns))
+    }
 
-                for f in fns {
-                    let name = f.name.clone();
-                    let local_f = f.clone();
+    /// Evaluate a script compiled with `compile` with own scope. This works
+    /// like `eval_with_scope`, without parsing the script again.
+    pub fn eval_ast<T: Any + Clone>(&mut self, scope: &mut Scope, ast: &AST) -> Result<T, EvalAltResult> {
+        let (mut x, _) = self.eval_ast_raw(scope, ast, &mut |_| true)?;
 
-                    let spec = FnSpec {
-                        ident: name,
-                        args: None,
-                    };
+        if TypeId::of::<T>() == TypeId::of::<()>() {
+            x = Box::new(());
+        }
 
-                    self.fns.insert(spec, Arc::new(FnIntExt::Int(local_f)));
-                }
+        match x.downcast::<T>() {
+            Ok(out) => Ok(*out),
+            Err(a) => Err(EvalAltResult::ErrorMismatchOutputType(self.nice_type_name(a))),
+        }
+    }
 
-                for o in os {
-                    x = match self.eval_stmt(scope, o) {
-                        Ok(v) => Ok(v),
-                        Err(e) => return Err(e),
-                    }
-                }
+    /// Evaluate a string, giving back its value whatever type it is
+    pub fn eval_dynamic(&mut self, input: &str) -> Result<Box<Any>, EvalAltResult> {
+        let mut scope: Scope = Vec::new();
+
+        self.eval_dynamic_with_scope(&mut scope, input)
+    }
+
+    /// Evaluate with own scope, giving back the value whatever type it is.
+    /// Unlike `eval_line`, a script ending in a statement gives `()` rather
+    /// than `None`.
+    ///
+    /// ```rust
+    /// use rhai::{Engine, Scope};
+    ///
+    /// let mut engine = Engine::new();
+    /// let mut scope = Scope::new();
+    ///
+    /// engine.eval_dynamic_with_scope(&mut scope, "let x = 40;").unwrap();
+    /// let value = engine.eval_dynamic_with_scope(&mut scope, "x + 2").unwrap();
+    ///
+    /// assert_eq!(value.downcast_ref::<i64>(), Some(&42));
+    /// ```
+    pub fn eval_dynamic_with_scope(
+        &mut self,
+        scope: &mut Scope,
+        input: &str,
+    ) -> Result<Box<Any>, EvalAltResult> {
+        self.eval_with_scope_raw(scope, input, &mut |_| true).map(|(x, _)| x)
+    }
+
+    /// Evaluate a line of input with own scope, the way a REPL would.
+    /// Returns the value of the script only if it ends in an expression, like
+    /// `x + 1`, and `None` if it ends in a statement, like `let x = 5;` or `x = 5`.
+    pub fn eval_line(
+        &mut self,
+        scope: &mut Scope,
+        input: &str,
+    ) -> Result<Option<Box<Any>>, EvalAltResult> {
+        self.eval_with_scope_raw(scope, input, &mut |_| true)
+            .map(|(x, is_expr)| if is_expr { Some(x) } else { None })
+    }
 
-                let x = x?;
+    /// Evaluate with own scope, calling `on_stmt` with the result of each
+    /// top-level statement as soon as it has run. Returning `false` from
+    /// `on_stmt` stops the script there. Function definitions aren't
+    /// statements, so they don't produce a result.
+    ///
+    /// ```rust
+    /// use rhai::{Engine, Scope};
+    ///
+    /// let mut engine = Engine::new();
+    /// let mut results = Vec::new();
+    ///
+    /// engine.eval_stepwise_with_scope(&mut Scope::new(), "let x = 2; x * 3; x + 1", |r| {
+    ///     results.push(r.is_ok());
+    ///     true
+    /// }).unwrap();
+    ///
+    /// assert_eq!(results.len(), 3);
+    /// ```
+    pub fn eval_stepwise_with_scope<F>(
+        &mut self,
+        scope: &mut Scope,
+        input: &str,
+        mut on_stmt: F,
+    ) -> Result<(), EvalAltResult>
+    where
+        F: FnMut(&Result<Box<Any>, EvalAltResult>) -> bool,
+    {
+        self.eval_with_scope_raw(scope, input, &mut |result| on_stmt(result)).map(|_| ())
+    }
+
+    // Returns the value of the script, and whether it ended in an expression.
+    fn eval_with_scope_raw(
+        &mut self,
+        scope: &mut Scope,
+        input: &str,
+        on_stmt: &mut FnMut(&mut Result<Box<Any>, EvalAltResult>) -> bool,
+    ) -> Result<(Box<Any>, bool), EvalAltResult> {
+        let ast = self.compile(input)?;
+
+        self.eval_ast_raw(scope, &ast, on_stmt)
+    }
 
-                match x.downcast::<T>() {
-                    Ok(out) => Ok(*out),
-                    Err(a) => Err(EvalAltResult::ErrorMismatchOutputType(self.nice_type_name(a))),
+    // `on_stmt` is given the result of each statement, and returns whether to
+    // go on. An error ends the script unless `on_stmt` takes it out.
+    fn eval_ast_raw(
+        &mut self,
+        scope: &mut Scope,
+        ast: &AST,
+        on_stmt: &mut FnMut(&mut Result<Box<Any>, EvalAltResult>) -> bool,
+    ) -> Result<(Box<Any>, bool), EvalAltResult> {
+        let AST(ref os, ref fns) = *ast;
+
+        let mut x: Result<Box<Any>, EvalAltResult> = Ok(Box::new(()));
+        let mut is_expr = false;
+        let replaced = self.define_script_fns(fns);
+
+        self.operations.set(0);
+
+        for o in os {
+            x = self.eval_stmt(scope, o).map_err(outside_loop);
+
+            is_expr = match *o {
+                Stmt::Expr(ref e) => match **e {
+                    Expr::Assignment(_, _) => false,
+                    _ => true,
+                },
+                _ => false,
+            };
+
+            // A `return` at the top level ends the script with that value
+            let returned = match x {
+                Err(EvalAltResult::Return(v)) => {
+                    x = Ok(v);
+                    is_expr = true;
+                    true
                 }
+                _ => false,
+            };
+
+            if !on_stmt(&mut x) || returned || x.is_err() {
+                break;
             }
-            Err(_) => Err(EvalAltResult::ErrorFunctionArgMismatch),
         }
+
+        self.forget_script_fns(replaced);
+
+        x.map(|x| (x, is_expr))
     }
 
     /// Evaluate a file, but only return errors, if there are any.
     /// Useful for when you don't need the result, but still need
     /// to keep track of possible errors
+    #[cfg(feature = "fs")]
     pub fn consume_file(&mut self, fname: &str) -> Result<(), EvalAltResult> {
         use std::fs::File;
         use std::io::prelude::*;
@@ -739,37 +2086,132 @@ impl Engine {
         scope: &mut Scope,
         input: &str,
     ) -> Result<(), EvalAltResult> {
-        let tokens = lex(input);
+        self.consume_counted(scope, input).map(|_| ()).map_err(|(_, e)| e)
+    }
 
-        let mut peekables = tokens.peekable();
-        let tree = parse(&mut peekables);
+    /// Like `consume`, but also report how many top-level statements ran
+    /// successfully, both when the script finishes and when it fails. A
+    /// statement which fails isn't counted, so on error the count is also the
+    /// index of the statement which broke. A script which doesn't parse runs
+    /// no statements.
+    ///
+    /// ```rust
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// assert_eq!(engine.consume_verbose("let x = 1; x += 1;"), Ok(2));
+    /// assert_eq!(engine.consume_verbose("let x = 1; y += 1; x += 1;").unwrap_err().0, 1);
+    /// ```
+    pub fn consume_verbose(&mut self, input: &str) -> Result<usize, (usize, EvalAltResult)> {
+        self.consume_counted(&mut Scope::new(), input)
+    }
 
-        match tree {
-            Ok((ref os, ref fns)) => {
-                for f in fns {
-                    if f.params.len() > 6 {
-                        return Ok(());
-                    }
-                    let name = f.name.clone();
-                    let local_f = f.clone();
+    /// Like `consume_with_scope`, but a statement which fails doesn't stop the
+    /// script. Every failure is returned along with the index of the top-level
+    /// statement it came from, so a config loader can apply all the valid
+    /// settings and report all the invalid ones at once. A script which
+    /// doesn't parse runs no statements, and running out of operations or a
+    /// top-level `return` still ends the script.
+    ///
+    /// ```rust
+    /// use rhai::{Engine, Scope};
+    ///
+    /// let mut engine = Engine::new();
+    /// let mut scope = Scope::new();
+    ///
+    /// let errors = engine.consume_collect_errors(&mut scope, "let a = 1; a = b; let c = 3; c = d;");
+    ///
+    /// assert_eq!(errors.iter().map(|&(i, _)| i).collect::<Vec<_>>(), vec![1, 3]);
+    /// assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "a + c"), Ok(4));
+    /// ```
+    pub fn consume_collect_errors(&mut self, scope: &mut Scope, input: &str) -> Vec<(usize, EvalAltResult)> {
+        let ast = match self.compile(input) {
+            Ok(ast) => ast,
+            Err(e) => return vec![(0, e)],
+        };
+        let mut errors = Vec::new();
+        let mut i = 0;
+
+        // Taking the error out of the result lets the script carry on
+        let _ = self.eval_ast_raw(scope, &ast, &mut |result| {
+            let keep_going = match *result {
+                Err(EvalAltResult::ErrorTooManyOperations) => false,
+                _ => true,
+            };
+
+            if result.is_err() {
+                let e = mem::replace(result, Ok(Box::new(()))).unwrap_err();
+                errors.push((i, e));
+            }
 
-                    let spec = FnSpec {
-                        ident: name,
-                        args: None,
-                    };
+            i += 1;
+            keep_going
+        });
 
-                    self.fns.insert(spec, Arc::new(FnIntExt::Int(local_f)));
-                }
+        errors
+    }
 
-                for o in os {
-                    if let Err(e) = self.eval_stmt(scope, o) {
-                        return Err(e);
-                    }
-                }
+    fn consume_counted(
+        &mut self,
+        scope: &mut Scope,
+        input: &str,
+    ) -> Result<usize, (usize, EvalAltResult)> {
+        let ast = self.compile(input).map_err(|e| (0, e))?;
+        let mut count = 0;
 
-                Ok(())
+        match self.eval_ast_raw(scope, &ast, &mut |result| {
+            if result.is_ok() {
+                count += 1;
             }
-            Err(_) => Err(EvalAltResult::ErrorFunctionArgMismatch),
+            true
+        }) {
+            Ok(_) => Ok(count),
+            Err(e) => Err((count, e)),
+        }
+    }
+
+    /// Make the functions defined in a script callable. Returns the definitions
+    /// they replaced, so that a sandboxed engine can undo them after the run.
+    fn define_script_fns(&mut self, fns: &[FnDef]) -> Vec<(FnSpec, Option<Arc<FnIntExt>>)> {
+        let mut replaced = Vec::new();
+
+        for f in fns {
+            let spec = FnSpec {
+                ident: f.name.clone(),
+                args: None,
+            };
+
+            let old = self.fns.insert(spec.clone(), Arc::new(FnIntExt::Int(f.clone())));
+            replaced.push((spec, old));
+        }
+
+        replaced
+    }
+
+    fn forget_script_fns(&mut self, replaced: Vec<(FnSpec, Option<Arc<FnIntExt>>)>) {
+        if !self.sandboxed {
+            return;
+        }
+
+        // Undo in reverse, in case a script defines the same function twice
+        for (spec, old) in replaced.into_iter().rev() {
+            match old {
+                Some(f) => self.fns.insert(spec, f),
+                None => self.fns.remove(&spec),
+            };
+        }
+    }
+
+    /// Parse a string without running it, and report code that can never be
+    /// reached, like statements following a `return` or `break` in the same block.
+    pub fn lint(&self, input: &str) -> Result<Vec<LintWarning>, EvalAltResult> {
+        let mut tokens = lex(input);
+        let tree = parse(&mut tokens);
+
+        match tree {
+            Ok((ref os, ref fns)) => Ok(lint(os, fns)),
+            Err(e) => Err(EvalAltResult::ErrorParsing(e)),
         }
     }
 
@@ -781,12 +2223,13 @@ impl Engine {
         engine.register_type_name::<i64>("integer");
         engine.register_type_name::<u64>("u64");
         engine.register_type_name::<u64>("usize");
-        engine.register_type_name::<f32>("f64");
+        engine.register_type_name::<f32>("f32");
         engine.register_type_name::<f64>("float");
         engine.register_type_name::<String>("string");
         engine.register_type_name::<char>("char");
         engine.register_type_name::<bool>("boolean");
         engine.register_type_name::<Vec<Box<Any>>>("array");
+        engine.register_type_name::<HashMap<String, Box<Any>>>("map");
 
         macro_rules! reg_op {
             ($engine:expr, $x:expr, $op:expr, $( $y:ty ),*) => (
@@ -826,29 +2269,51 @@ impl Engine {
         fn and(x: bool, y: bool) -> bool { x && y }
         fn or(x: bool, y: bool) -> bool  { x || y }
         fn not(x: bool) -> bool { !x }
+        fn bitwise_not<T: Not>(x: T) -> <T as Not>::Output { !x }
         fn concat(x: String, y: String) -> String { x + &y }
+        fn push_str(x: &mut String, y: String) { x.push_str(&y) }
         fn binary_and<T: BitAnd>(x: T, y: T) -> <T as BitAnd>::Output  { x & y }
         fn binary_or<T: BitOr>(x: T, y: T) -> <T as BitOr>::Output     { x | y }
         fn binary_xor<T: BitXor>(x: T, y: T) -> <T as BitXor>::Output  { x ^ y }
         fn left_shift<T: Shl<T>>(x: T, y: T) -> <T as Shl<T>>::Output  { x.shl(y) }
         fn right_shift<T: Shr<T>>(x: T, y: T) -> <T as Shr<T>>::Output { x.shr(y) }
-        fn modulo<T: Rem<T>>(x: T, y: T) -> <T as Rem<T>>::Output { x % y }
-        fn pow_i64_i64(x: i64, y: i64) -> i64 { x.pow(y as u32) }
+        fn pow_f32_f32(x: f32, y: f32) -> f32 { x.powf(y) }
         fn pow_f64_f64(x: f64, y: f64) -> f64 { x.powf(y) }
         fn pow_f64_i64(x: f64, y: i64) -> f64 { x.powi(y as i32) }
         fn unit_eq(a: (), b: ()) -> bool { true }
+        // An empty range (`lo > hi`) clamps everything to `lo`
+        fn clamp<T: PartialOrd>(x: T, lo: T, hi: T) -> T {
+            if lo > hi || x < lo { lo } else if x > hi { hi } else { x }
+        }
+        fn lerp(a: f64, b: f64, t: f64) -> f64 { a + (b - a) * t }
+        // Arguments are already copied on the way in, so this only hands the copy back
+        fn clone_value<T>(x: T) -> T { x }
 
         reg_op!(engine, "+", add, i32, i64, u32, u64, f32, f64);
         reg_op!(engine, "-", sub, i32, i64, u32, u64, f32, f64);
         reg_op!(engine, "*", mul, i32, i64, u32, u64, f32, f64);
-        reg_op!(engine, "/", div, i32, i64, u32, u64, f32, f64);
+        reg_op!(engine, "/", div, f32, f64);
+
+        reg_cmp!(engine, "<", lt, i32, i64, u32, u64, String, f32, f64);
+        reg_cmp!(engine, "<=", lte, i32, i64, u32, u64, String, f32, f64);
+        reg_cmp!(engine, ">", gt, i32, i64, u32, u64, String, f32, f64);
+        reg_cmp!(engine, ">=", gte, i32, i64, u32, u64, String, f32, f64);
+        reg_cmp!(engine, "==", eq, i32, i64, u32, u64, bool, String, f32, f64);
+        reg_cmp!(engine, "!=", ne, i32, i64, u32, u64, bool, String, f32, f64);
+
+        // Host types often use `f32` while float literals in scripts are `f64`,
+        // so when the two meet the `f32` is widened and the result is an `f64`
+        macro_rules! reg_f32_f64 {
+            ($engine:expr, $( $x:expr => $op:expr ),*) => (
+                $(
+                    $engine.register_fn($x, |x: f32, y: f64| $op(x as f64, y));
+                    $engine.register_fn($x, |x: f64, y: f32| $op(x, y as f64));
+                )*
+            )
+        }
 
-        reg_cmp!(engine, "<", lt, i32, i64, u32, u64, String, f64);
-        reg_cmp!(engine, "<=", lte, i32, i64, u32, u64, String, f64);
-        reg_cmp!(engine, ">", gt, i32, i64, u32, u64, String, f64);
-        reg_cmp!(engine, ">=", gte, i32, i64, u32, u64, String, f64);
-        reg_cmp!(engine, "==", eq, i32, i64, u32, u64, bool, String, f64);
-        reg_cmp!(engine, "!=", ne, i32, i64, u32, u64, bool, String, f64);
+        reg_f32_f64!(engine, "+" => add, "-" => sub, "*" => mul, "/" => div,
+                     "<" => lt, "<=" => lte, ">" => gt, ">=" => gte, "==" => eq, "!=" => ne);
 
         reg_op!(engine, "||", or, bool);
         reg_op!(engine, "&&", and, bool);
@@ -859,17 +2324,421 @@ impl Engine {
         reg_op!(engine, "^", binary_xor, i32, i64, u32, u64);
         reg_op!(engine, "<<", left_shift, i32, i64, u32, u64);
         reg_op!(engine, ">>", right_shift, i32, i64, u32, u64);
-        reg_op!(engine, "%", modulo, i32, i64, u32, u64);
-        engine.register_fn("~", pow_i64_i64);
+        engine.register_fn("~", pow_f32_f32);
         engine.register_fn("~", pow_f64_f64);
         engine.register_fn("~", pow_f64_i64);
 
         reg_un!(engine, "-", neg, i32, i64, f32, f64);
+
+        // Only zero can be negated without leaving the range of an unsigned type
+        macro_rules! reg_neg_unsigned {
+            ($engine:expr, $( $y:ty ),*) => (
+                $(
+                    $engine.register_fn_raw("-".into(), Some(vec![TypeId::of::<$y>()]),
+                        Box::new(|args: Vec<&mut Any>| match args[0].downcast_ref::<$y>() {
+                            Some(&0) => Ok(Box::new(0 as $y) as Box<Any>),
+                            Some(_) => Err(EvalAltResult::ErrorArithmetic(
+                                "Cannot negate unsigned value".into())),
+                            None => Err(EvalAltResult::ErrorFunctionArgMismatch),
+                        }));
+                )*
+            )
+        }
+
+        reg_neg_unsigned!(engine, u32, u64);
+
+        // Integer powers follow Rust's `pow`, so `0 ~ 0` is 1, but fail on a
+        // negative exponent or a result that doesn't fit instead of panicking
+        macro_rules! reg_pow_int {
+            ($engine:expr, $( $y:ty ),*) => (
+                $(
+                    $engine.register_fn_raw("~".into(), Some(vec![TypeId::of::<$y>(), TypeId::of::<$y>()]),
+                        Box::new(|args: Vec<&mut Any>| {
+                            use std::convert::TryFrom;
+
+                            let (x, y) = match (args[0].downcast_ref::<$y>(), args[1].downcast_ref::<$y>()) {
+                                (Some(&x), Some(&y)) => (x, y),
+                                _ => return Err(EvalAltResult::ErrorFunctionArgMismatch),
+                            };
+
+                            match i64::try_from(y) {
+                                Ok(y) if y < 0 => Err(EvalAltResult::ErrorArithmetic(
+                                    format!("Cannot raise an integer to a negative power: {}", y))),
+                                Ok(y) if y <= u32::max_value() as i64 => x.checked_pow(y as u32)
+                                    .map(|result| Box::new(result) as Box<Any>)
+                                    .ok_or_else(|| EvalAltResult::ErrorArithmetic(
+                                        format!("Power overflow: {} ~ {}", x, y))),
+                                _ => Err(EvalAltResult::ErrorArithmetic(format!("Power overflow: {} ~ {}", x, y))),
+                            }
+                        }));
+                )*
+            )
+        }
+
+        reg_pow_int!(engine, i32, i64, u32, u64);
+
+        // Dividing an integer by zero, or the smallest signed value by -1,
+        // would panic, so these fail with an `ErrorArithmetic` instead
+        macro_rules! reg_div_int {
+            ($engine:expr, $( $y:ty ),*) => (
+                $(
+                    let ops: [(&str, fn($y, $y) -> Option<$y>); 2] = [
+                        ("/", |x, y| x.checked_div(y)),
+                        ("%", |x, y| x.checked_rem(y)),
+                    ];
+
+                    for &(name, op) in &ops {
+                        $engine.register_fn_raw(name.into(), Some(vec![TypeId::of::<$y>(), TypeId::of::<$y>()]),
+                            Box::new(move |args: Vec<&mut Any>| {
+                                match (args[0].downcast_ref::<$y>(), args[1].downcast_ref::<$y>()) {
+                                    (Some(&x), Some(&0)) => Err(EvalAltResult::ErrorArithmetic(
+                                        format!("Division by zero: {} {} 0", x, name))),
+                                    (Some(&x), Some(&y)) => op(x, y)
+                                        .map(|result| Box::new(result) as Box<Any>)
+                                        .ok_or_else(|| EvalAltResult::ErrorArithmetic(
+                                            format!("Division overflow: {} {} {}", x, name, y))),
+                                    _ => Err(EvalAltResult::ErrorFunctionArgMismatch),
+                                }
+                            }));
+                    }
+                )*
+            )
+        }
+
+        reg_div_int!(engine, i32, i64, u32, u64);
+
+        // Pads with zeros on the left. A number longer than `width` is left as it is.
+        fn pad_digits(digits: String, width: i64) -> Result<Box<Any>, EvalAltResult> {
+            if width < 0 || width > 64 {
+                return Err(EvalAltResult::ErrorRuntime(format!("Cannot pad a number to {} digits", width)));
+            }
+
+            Ok(Box::new(format!("{:0>1$}", digits, width as usize)))
+        }
+
+        // Negative numbers show their two's complement bits, as Rust's `{:x}` does
+        macro_rules! reg_to_radix {
+            ($engine:expr, $( $y:ty ),*) => (
+                $(
+                    let formats: [(&str, fn($y) -> String); 3] = [
+                        ("to_hex", |x| format!("{:x}", x)),
+                        ("to_octal", |x| format!("{:o}", x)),
+                        ("to_binary", |x| format!("{:b}", x)),
+                    ];
+
+                    for &(name, format) in &formats {
+                        $engine.register_fn(name, format);
+                        $engine.register_fn_raw(name.into(), Some(vec![TypeId::of::<$y>(), TypeId::of::<i64>()]),
+                            Box::new(move |args: Vec<&mut Any>| {
+                                match (args[0].downcast_ref::<$y>(), args[1].downcast_ref::<i64>()) {
+                                    (Some(&x), Some(&width)) => pad_digits(format(x), width),
+                                    _ => Err(EvalAltResult::ErrorFunctionArgMismatch),
+                                }
+                            }));
+                    }
+                )*
+            )
+        }
+
+        reg_to_radix!(engine, i32, i64, u32, u64);
+
+        fn parse_int(s: &str, radix: i64) -> Result<Box<Any>, EvalAltResult> {
+            if radix < 2 || radix > 36 {
+                return Err(EvalAltResult::ErrorRuntime(format!("Invalid radix: {}", radix)));
+            }
+
+            i64::from_str_radix(s.trim(), radix as u32)
+                .map(|x| Box::new(x) as Box<Any>)
+                .map_err(|_| EvalAltResult::ErrorRuntime(format!("Cannot parse '{}' as an integer", s)))
+        }
+
+        fn parse_float(s: &str) -> Result<Box<Any>, EvalAltResult> {
+            s.trim()
+                .parse::<f64>()
+                .map(|x| Box::new(x) as Box<Any>)
+                .map_err(|_| EvalAltResult::ErrorRuntime(format!("Cannot parse '{}' as a float", s)))
+        }
+
+        // Rounds to `decimals` places, e.g. 19.99 for a price. The limit keeps
+        // a bad argument from building a huge string.
+        fn format_float(x: f64, decimals: i64) -> Result<Box<Any>, EvalAltResult> {
+            if decimals < 0 || decimals > 100 {
+                return Err(EvalAltResult::ErrorRuntime(
+                    format!("Cannot format a float with {} decimals", decimals)));
+            }
+
+            Ok(Box::new(format!("{:.*}", decimals as usize, x)))
+        }
+
+        // A count of zero or less gives an empty string
+        fn repeat(s: &str, count: i64, max_size: usize) -> Result<Box<Any>, EvalAltResult> {
+            let count = if count < 0 { 0 } else { count as usize };
+
+            match s.len().checked_mul(count) {
+                Some(len) if len <= max_size => Ok(Box::new(s.repeat(count))),
+                _ => Err(EvalAltResult::ErrorRuntime(
+                    format!("Cannot repeat a string {} times", count))),
+            }
+        }
+
+        for name in &["repeat", "*"] {
+            engine.register_fn_with_engine(name, Some(vec![TypeId::of::<String>(), TypeId::of::<i64>()]),
+                Box::new(|e, args| match (args[0].downcast_ref::<String>(), args[1].downcast_ref::<i64>()) {
+                    (Some(s), Some(&count)) => {
+                        e.count_operations(if count < 0 { 0 } else { count as u64 })?;
+                        repeat(s, count, e.max_string_size)
+                    }
+                    _ => Err(EvalAltResult::ErrorFunctionArgMismatch),
+                }));
+        }
+
+        engine.register_fn_raw("parse_int".into(), Some(vec![TypeId::of::<String>()]),
+            Box::new(|args: Vec<&mut Any>| match args[0].downcast_ref::<String>() {
+                Some(s) => parse_int(s, 10),
+                None => Err(EvalAltResult::ErrorFunctionArgMismatch),
+            }));
+        engine.register_fn_raw("parse_int".into(), Some(vec![TypeId::of::<String>(), TypeId::of::<i64>()]),
+            Box::new(|args: Vec<&mut Any>| match (args[0].downcast_ref::<String>(), args[1].downcast_ref::<i64>()) {
+                (Some(s), Some(&radix)) => parse_int(s, radix),
+                _ => Err(EvalAltResult::ErrorFunctionArgMismatch),
+            }));
+        engine.register_fn_raw("format_float".into(), Some(vec![TypeId::of::<f64>(), TypeId::of::<i64>()]),
+            Box::new(|args: Vec<&mut Any>| match (args[0].downcast_ref::<f64>(), args[1].downcast_ref::<i64>()) {
+                (Some(&x), Some(&decimals)) => format_float(x, decimals),
+                _ => Err(EvalAltResult::ErrorFunctionArgMismatch),
+            }));
+        engine.register_fn_raw("parse_float".into(), Some(vec![TypeId::of::<String>()]),
+            Box::new(|args: Vec<&mut Any>| match args[0].downcast_ref::<String>() {
+                Some(s) => parse_float(s),
+                None => Err(EvalAltResult::ErrorFunctionArgMismatch),
+            }));
+
+        engine.register_fn_for_any("to_string", |e, x| Ok(Box::new(e.format_value(x, false))));
+        engine.register_fn_for_any("debug", |e, x| Ok(Box::new(e.format_value(x, true))));
+
+        engine.register_constant("PI", ::std::f64::consts::PI);
+        engine.register_constant("E", ::std::f64::consts::E);
         reg_un!(engine, "!", not, bool);
+        reg_un!(engine, "~", bitwise_not, i32, i64, u32, u64);
+        engine.register_fn("clamp", clamp as fn(x: i64, lo: i64, hi: i64) -> i64);
+        engine.register_fn("clamp", clamp as fn(x: f64, lo: f64, hi: f64) -> f64);
+        engine.register_fn("lerp", lerp);
+        reg_un!(engine, "clone", clone_value, i32, i64, u32, u64, f32, f64, bool, char, String,
+                Vec<Box<Any>>);
 
         engine.register_fn("+", concat);
+        engine.register_fn("+=", push_str);
+        engine.register_fn("push_str", push_str);
         engine.register_fn("==", unit_eq);
 
+        // `()` is only equal to itself, so `x == ()` works whatever the type
+        // of `x`. Other comparisons between different types stay an error.
+        fn unit_cmp(engine: &Engine, op: &str, args: Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult> {
+            if args.len() == 2 && (args[0].is::<()>() || args[1].is::<()>()) {
+                let equal = args[0].is::<()>() && args[1].is::<()>();
+                return Ok(Box::new(if op == "==" { equal } else { !equal }));
+            }
+
+            engine.call_unknown_fn(op, args)
+        }
+
+        for op in &["==", "!="] {
+            engine.register_fn_with_engine(op, None, Box::new(move |e, args| unit_cmp(e, op, args)));
+        }
+        engine.register_fn_for_any("is_unit", |_, x| Ok(Box::new(x.is::<()>())));
+
+        fn array_arg<'a>(args: &'a mut Vec<&mut Any>) -> &'a mut Vec<Box<Any>> {
+            args[0].downcast_mut().unwrap()
+        }
+
+        fn array_sum(engine: &Engine, mut args: Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult> {
+            let arr = array_arg(&mut args);
+            let mut items = arr.iter().map(|x| engine.copy_value(x.as_ref()));
+
+            let mut total = match items.next() {
+                Some(first) => first,
+                None => return Ok(Box::new(0i64)),
+            };
+
+            for mut item in items {
+                total = engine.call_fn_raw("+".into(), vec![total.as_mut(), item.as_mut()])?;
+            }
+
+            Ok(total)
+        }
+
+        // Picks the element for which `op(element, current)` holds, so `<` gives the minimum.
+        fn array_pick(
+            engine: &Engine,
+            op: &str,
+            mut args: Vec<&mut Any>,
+        ) -> Result<Box<Any>, EvalAltResult> {
+            let arr = array_arg(&mut args);
+            let mut items = arr.iter().map(|x| engine.copy_value(x.as_ref()));
+
+            let mut current = match items.next() {
+                Some(first) => first,
+                None => {
+                    return Err(EvalAltResult::ErrorArithmetic(
+                        "Cannot take the minimum or maximum of an empty array".into(),
+                    ))
+                }
+            };
+
+            for mut item in items {
+                let better = engine
+                    .call_fn_raw(op.into(), vec![item.as_mut(), current.as_mut()])?
+                    .downcast::<bool>()
+                    .map_err(|_| EvalAltResult::ErrorFunctionArgMismatch)?;
+
+                if *better {
+                    current = item;
+                }
+            }
+
+            Ok(current)
+        }
+
+        // Arrays of different lengths, or with elements of different types at the same
+        // position, are unequal. Elements of the same type are compared with their `==`.
+        fn array_eq(engine: &Engine, mut args: Vec<&mut Any>) -> Result<bool, EvalAltResult> {
+            let rhs = args.pop().and_then(|x| x.downcast_mut::<Vec<Box<Any>>>())
+                .ok_or(EvalAltResult::ErrorFunctionArgMismatch)?;
+            let lhs = array_arg(&mut args);
+
+            if lhs.len() != rhs.len() {
+                return Ok(false);
+            }
+
+            for (a, b) in lhs.iter_mut().zip(rhs.iter_mut()) {
+                if <Any as Any>::type_id(&**a) != <Any as Any>::type_id(&**b) {
+                    return Ok(false);
+                }
+
+                let equal = engine
+                    .call_fn_raw("==".into(), vec![a.as_mut(), b.as_mut()])?
+                    .downcast::<bool>()
+                    .map_err(|_| EvalAltResult::ErrorFunctionArgMismatch)?;
+
+                if !*equal {
+                    return Ok(false);
+                }
+            }
+
+            Ok(true)
+        }
+
+        // Copies go through the registered `clone`, so custom types need one to be filled
+        fn array_fill(engine: &Engine, mut args: Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult> {
+            if args.len() != 2 {
+                return Err(EvalAltResult::ErrorFunctionArgMismatch);
+            }
+
+            let count = *args.pop().unwrap().downcast_ref::<i64>()
+                .ok_or(EvalAltResult::ErrorFunctionArgMismatch)?;
+            let value = args.pop().unwrap();
+
+            if count < 0 || count as u64 > engine.max_array_size as u64 {
+                return Err(EvalAltResult::ErrorRuntime(
+                    format!("Cannot fill an array with {} elements", count)));
+            }
+
+            let mut arr: Vec<Box<Any>> = Vec::with_capacity(count as usize);
+
+            for _ in 0..count {
+                arr.push(engine.call_fn_raw("clone".into(), vec![&mut *value])?);
+            }
+
+            Ok(Box::new(arr))
+        }
+
+        // Scripts can't create maps yet, but can work with ones the host puts in the
+        // scope. `keys` and `values` list entries in the same order for the same map.
+        fn map_keys(map: &mut HashMap<String, Box<Any>>) -> Vec<Box<Any>> {
+            map.keys().map(|k| Box::new(k.clone()) as Box<Any>).collect()
+        }
+        fn map_values(engine: &Engine, mut args: Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult> {
+            let map = args[0].downcast_mut::<HashMap<String, Box<Any>>>().unwrap();
+
+            Ok(Box::new(map.values().map(|x| engine.copy_value(x.as_ref())).collect::<Vec<_>>()))
+        }
+        fn map_has_key(map: &mut HashMap<String, Box<Any>>, key: String) -> bool {
+            map.contains_key(&key)
+        }
+        // Gives back the removed value, or `()` if there was none
+        fn map_remove(map: &mut HashMap<String, Box<Any>>, key: String) -> Box<Any> {
+            map.remove(&key).unwrap_or_else(|| Box::new(()))
+        }
+
+        // Counts chars rather than bytes, the same as `len`, or -1 if `sub` isn't in `s`
+        fn index_of(s: &mut String, sub: String) -> i64 {
+            s.find(sub.as_str()).map_or(-1, |i| s[..i].chars().count() as i64)
+        }
+
+        engine.register_fn("len", |s: &mut String| s.chars().count() as i64);
+        engine.register_fn("contains", |s: &mut String, sub: String| s.contains(sub.as_str()));
+        engine.register_fn("starts_with", |s: &mut String, prefix: String| s.starts_with(prefix.as_str()));
+        engine.register_fn("ends_with", |s: &mut String, suffix: String| s.ends_with(suffix.as_str()));
+        engine.register_fn("index_of", index_of);
+        engine.register_fn("len", |arr: &mut Vec<Box<Any>>| arr.len() as i64);
+        engine.register_fn("len", |map: &mut HashMap<String, Box<Any>>| map.len() as i64);
+        engine.register_fn("keys", map_keys);
+        engine.register_fn_with_engine("values", Some(vec![TypeId::of::<HashMap<String, Box<Any>>>()]),
+                                       Box::new(map_values));
+        engine.register_fn("has_key", map_has_key);
+        engine.register_fn("remove", map_remove);
+
+        // These take `&mut` like every method, but only read it, so calling
+        // one on a property doesn't pass the property back to its setter
+        let string = TypeId::of::<String>();
+        let map = TypeId::of::<HashMap<String, Box<Any>>>();
+        engine.read_only_fns.extend(vec![
+            FnSpec::new("len", vec![string]),
+            FnSpec::new("len", vec![TypeId::of::<Vec<Box<Any>>>()]),
+            FnSpec::new("len", vec![map]),
+            FnSpec::new("contains", vec![string, string]),
+            FnSpec::new("starts_with", vec![string, string]),
+            FnSpec::new("ends_with", vec![string, string]),
+            FnSpec::new("index_of", vec![string, string]),
+            FnSpec::new("keys", vec![map]),
+            FnSpec::new("has_key", vec![map, string]),
+        ]);
+
+        // `call(name, args...)` calls the function called `name`, which lets
+        // data given to a script pick what it does
+        fn call_by_name(engine: &Engine, mut args: Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult> {
+            if args.is_empty() {
+                return Err(EvalAltResult::ErrorFunctionArgMismatch);
+            }
+
+            let name = args.remove(0).downcast_ref::<String>().cloned()
+                .ok_or(EvalAltResult::ErrorFunctionArgMismatch)?;
+
+            engine.call_fn_raw(name, args)
+        }
+
+        engine.register_fn_with_engine("call", None, Box::new(call_by_name));
+
+        engine.register_fn("new_array", || Vec::<Box<Any>>::new());
+        // The value can be of any type, so like a script function this is
+        // found by name alone
+        engine.register_fn_with_engine("fill", None, Box::new(array_fill));
+
+        let array_type = vec![TypeId::of::<Vec<Box<Any>>>()];
+        engine.register_fn_with_engine("sum", Some(array_type.clone()), Box::new(array_sum));
+        engine.register_fn_with_engine("min", Some(array_type.clone()),
+                                       Box::new(|e, args| array_pick(e, "<", args)));
+        engine.register_fn_with_engine("max", Some(array_type.clone()), Box::new(|e, args| array_pick(e, ">", args)));
+
+        let array_pair = vec![array_type[0], array_type[0]];
+        engine.register_fn_with_engine("==", Some(array_pair.clone()),
+                                       Box::new(|e, args| array_eq(e, args).map(|x| Box::new(x) as Box<Any>)));
+        engine.register_fn_with_engine("!=", Some(array_pair),
+                                       Box::new(|e, args| array_eq(e, args).map(|x| Box::new(!x) as Box<Any>)));
+
+        #[cfg(feature = "rand")]
+        ::random::register_rand(engine);
+
         // engine.register_fn("[]", idx);
         // FIXME?  Registering array lookups are a special case because we want to return boxes
         // directly let ent = engine.fns.entry("[]".to_string()).or_insert_with(Vec::new);
@@ -879,13 +2748,39 @@ impl Engine {
 
     /// Make a new engine
     pub fn new() -> Engine {
-        let mut engine = Engine {
-            fns: HashMap::new(),
-            type_names: HashMap::new(),
-        };
+        let mut engine = Engine::new_raw();
 
         Engine::register_default_lib(&mut engine);
 
         engine
     }
+
+    /// Make a new engine without any functions registered
+    pub fn new_raw() -> Engine {
+        Engine {
+            fns: HashMap::new(),
+            type_names: HashMap::new(),
+            constants: HashMap::new(),
+            sandboxed: false,
+            strict_types: false,
+            max_call_depth: 64,
+            max_array_size: MAX_ARRAY_SIZE,
+            max_string_size: MAX_STRING_SIZE,
+            max_operations: None,
+            formatters: HashMap::new(),
+            cloners: HashMap::new(),
+            unknown_fn: None,
+            fns_for_any: HashMap::new(),
+            read_only_fns: HashSet::new(),
+            operations: Cell::new(0),
+            call_depth: Cell::new(0),
+            #[cfg(feature = "rand")]
+            rng: Cell::new(::random::time_seed()),
+        }
+    }
+
+    /// Start configuring a new engine, see `EngineBuilder`
+    pub fn builder() -> EngineBuilder {
+        EngineBuilder::new()
+    }
 }
@@ -1,12 +1,25 @@
 use std::any::TypeId;
+use std::cell::RefCell;
 
 use any::Any;
-use engine::{Engine, EvalAltResult};
+use engine::{Engine, EvalAltResult, FnSpec};
 
 pub trait RegisterFn<FN, ARGS, RET> {
     fn register_fn(&mut self, name: &str, f: FN);
 }
 
+/// Like `RegisterFn`, but for closures which change their captured state
+/// when called, such as counters or seeded random number generators.
+pub trait RegisterFnMut<FN, ARGS, RET> {
+    fn register_fn_mut(&mut self, name: &str, f: FN);
+}
+
+/// Like `RegisterFn`, but for functions which can fail. An `Err` they
+/// return ends the script with that error.
+pub trait RegisterResultFn<FN, ARGS, RET> {
+    fn register_result_fn(&mut self, name: &str, f: FN);
+}
+
 pub struct Ref<A>(A);
 pub struct Mut<A>(A);
 
@@ -17,9 +30,11 @@ macro_rules! count_args {
 
 macro_rules! def_register {
     () => {
-        def_register!(imp);
+        def_register!(imp true;);
     };
-    (imp $($par:ident => $mark:ty => $param:ty => $clone:expr),*) => {
+    // `$read_only` is whether the first parameter, which a method is called
+    // on, is taken by value or by `&` rather than by `&mut`
+    (imp $read_only:expr; $($par:ident => $mark:ty => $param:ty => $clone:expr),*) => {
         impl<$($par,)* FN, RET> RegisterFn<FN, ($($mark,)*), RET> for Engine
         where
             $($par: Any + Clone,)*
@@ -45,16 +60,86 @@ macro_rules! def_register {
                     // potentially clone the value, otherwise pass the reference.
                     Ok(Box::new(f($(($clone)($par)),*)) as Box<Any>)
                 };
-                self.register_fn_raw(name.to_owned(), Some(vec![$(TypeId::of::<$par>()),*]), Box::new(fun));
+                let args = vec![$(TypeId::of::<$par>()),*];
+                self.register_fn_raw(name.to_owned(), Some(args.clone()), Box::new(fun));
+
+                if $read_only {
+                    self.read_only_fns.insert(FnSpec::new(name, args));
+                }
+            }
+        }
+
+        impl<$($par,)* FN, RET> RegisterFnMut<FN, ($($mark,)*), RET> for Engine
+        where
+            $($par: Any + Clone,)*
+            FN: FnMut($($param),*) -> RET + 'static,
+            RET: Any,
+        {
+            fn register_fn_mut(&mut self, name: &str, f: FN) {
+                let f = RefCell::new(f);
+                let fn_name = name.to_owned();
+
+                let fun = move |mut args: Vec<&mut Any>| {
+                    if args.len() != count_args!($($par)*) {
+                        return Err(EvalAltResult::ErrorFunctionArgMismatch);
+                    }
+
+                    let mut drain = args.drain(..);
+                    $(
+                    let $par = ((*drain.next().unwrap()).downcast_mut() as Option<&mut $par>)
+                        .ok_or(EvalAltResult::ErrorFunctionArgMismatch)?;
+                    )*
+
+                    let mut f = f.try_borrow_mut().map_err(|_| {
+                        EvalAltResult::ErrorRuntime(format!("Function '{}' called itself", fn_name))
+                    })?;
+
+                    Ok(Box::new((&mut *f)($(($clone)($par)),*)) as Box<Any>)
+                };
+                let args = vec![$(TypeId::of::<$par>()),*];
+                self.register_fn_raw(name.to_owned(), Some(args.clone()), Box::new(fun));
+
+                if $read_only {
+                    self.read_only_fns.insert(FnSpec::new(name, args));
+                }
+            }
+        }
+
+        impl<$($par,)* FN, RET> RegisterResultFn<FN, ($($mark,)*), RET> for Engine
+        where
+            $($par: Any + Clone,)*
+            FN: Fn($($param),*) -> Result<RET, EvalAltResult> + 'static,
+            RET: Any,
+        {
+            fn register_result_fn(&mut self, name: &str, f: FN) {
+                let fun = move |mut args: Vec<&mut Any>| {
+                    if args.len() != count_args!($($par)*) {
+                        return Err(EvalAltResult::ErrorFunctionArgMismatch);
+                    }
+
+                    let mut drain = args.drain(..);
+                    $(
+                    let $par = ((*drain.next().unwrap()).downcast_mut() as Option<&mut $par>)
+                        .ok_or(EvalAltResult::ErrorFunctionArgMismatch)?;
+                    )*
+
+                    f($(($clone)($par)),*).map(|r| Box::new(r) as Box<Any>)
+                };
+                let args = vec![$(TypeId::of::<$par>()),*];
+                self.register_fn_raw(name.to_owned(), Some(args.clone()), Box::new(fun));
+
+                if $read_only {
+                    self.read_only_fns.insert(FnSpec::new(name, args));
+                }
             }
         }
 
         //def_register!(imp_pop $($par => $mark => $param),*);
     };
     ($p0:ident $(, $p:ident)*) => {
-        def_register!(imp $p0 => $p0 => $p0 => Clone::clone $(, $p => $p => $p => Clone::clone)*);
-        def_register!(imp $p0 => Ref<$p0> => &$p0 => |x| { x } $(, $p => $p => $p => Clone::clone)*);
-        def_register!(imp $p0 => Mut<$p0> => &mut $p0 => |x| { x } $(, $p => $p => $p => Clone::clone)*);
+        def_register!(imp true; $p0 => $p0 => $p0 => Clone::clone $(, $p => $p => $p => Clone::clone)*);
+        def_register!(imp true; $p0 => Ref<$p0> => &$p0 => |x| { x } $(, $p => $p => $p => Clone::clone)*);
+        def_register!(imp false; $p0 => Mut<$p0> => &mut $p0 => |x| { x } $(, $p => $p => $p => Clone::clone)*);
 
         def_register!($($p),*);
     };
@@ -64,5 +149,5 @@ macro_rules! def_register {
 //    };
 }
 
-#[cfg_attr(rustfmt, rustfmt_skip)]
+#[rustfmt::skip]
 def_register!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S);
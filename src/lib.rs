@@ -25,6 +25,7 @@
 //!
 //! let mut engine = Engine::new();
 //! engine.register_fn("compute_something", compute_something);
+//! # #[cfg(feature = "fs")]
 //! assert_eq!(engine.eval_file::<bool>("my_script.rhai"), Ok(true));
 //! ```
 //!
@@ -42,12 +43,18 @@ macro_rules! debug_println {
 }
 
 mod any;
+mod builder;
 mod call;
 mod engine;
 mod fn_register;
 mod parser;
+#[cfg(feature = "rand")]
+mod random;
 
 pub use any::Any;
-pub use engine::{Engine, EvalAltResult, Scope};
-pub use fn_register::RegisterFn;
+pub use builder::EngineBuilder;
+pub use engine::{Engine, EvalAltResult, Scope, ScopeExt};
+pub use fn_register::{RegisterFn, RegisterFnMut, RegisterResultFn};
+pub use parser::{tokenize, tokenize_at, LexError, LintWarning, ParseError, Position, Span, Token,
+                 AST, MAX_EXPR_DEPTH};
 
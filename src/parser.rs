@@ -1,15 +1,16 @@
 use std::error::Error;
 use std::fmt;
-use std::iter::Peekable;
 use std::str::Chars;
 use std::char;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum LexError {
     UnexpectedChar,
     MalformedEscapeSequence,
     MalformedNumber,
     MalformedChar,
+    MalformedIdentifier,
+    UnterminatedComment,
     Nothing
 }
 
@@ -20,6 +21,8 @@ impl Error for LexError {
             LexError::MalformedEscapeSequence => "Unexpected values in escape sequence",
             LexError::MalformedNumber => "Unexpected characters in number",
             LexError::MalformedChar => "Char constant not a single character",
+            LexError::MalformedIdentifier => "Identifier in backticks empty or not closed",
+            LexError::UnterminatedComment => "Block comment not closed",
             LexError::Nothing => "This error is for internal use only"
         }
     }
@@ -31,7 +34,7 @@ impl fmt::Display for LexError {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ParseError {
     BadInput,
     InputPastEndOfFile,
@@ -40,11 +43,22 @@ pub enum ParseError {
     MissingLCurly,
     MissingRCurly,
     MissingRSquare,
+    /// Where the token found instead of `;` is
+    MissingSemicolon(Position),
+    MissingColon,
+    TrailingInput,
     MalformedCallExpr,
     MalformedIndexExpr,
     VarExpectsIdentifier,
     FnMissingName,
     FnMissingParams,
+    FnDuplicateParam(String),
+    ForMissingIn,
+    VarMissingType,
+    VarTypeWithoutValue,
+    ExprTooDeep,
+    /// Where the comment which isn't closed starts
+    UnterminatedComment(Position),
 }
 
 impl Error for ParseError {
@@ -57,20 +71,61 @@ impl Error for ParseError {
             ParseError::MissingLCurly => "Expected '{'",
             ParseError::MissingRCurly => "Expected '}'",
             ParseError::MissingRSquare => "Expected ']'",
+            ParseError::MissingSemicolon(_) => "Expected ';' between statements",
+            ParseError::MissingColon => "Expected ':' after the first branch of '?'",
+            ParseError::TrailingInput => "Unexpected closing bracket after the end of the script",
             ParseError::MalformedCallExpr => "Call contains bad expression",
             ParseError::MalformedIndexExpr => "Indexing expression missing correct index",
             ParseError::VarExpectsIdentifier => "'var' expects the name of a variable",
             ParseError::FnMissingName => "Function declaration is missing name",
             ParseError::FnMissingParams => "Function declaration is missing parameters",
+            ParseError::FnDuplicateParam(_) => "Function declaration has the same parameter twice",
+            ParseError::ForMissingIn => "Expected 'in' after the variable of a 'for' loop",
+            ParseError::VarMissingType => "Expected a type name after ':'",
+            ParseError::VarTypeWithoutValue => "A variable declared with a type needs a value",
+            ParseError::ExprTooDeep => "Expressions and blocks nested more than MAX_EXPR_DEPTH deep",
+            ParseError::UnterminatedComment(_) => "Block comment not closed",
         }
     }
 
     fn cause(&self) -> Option<&Error> { None }
 }
 
+impl ParseError {
+    /// Where in the script the error was found, if that is known
+    pub fn position(&self) -> Option<Position> {
+        match *self {
+            ParseError::MissingSemicolon(pos) | ParseError::UnterminatedComment(pos) => Some(pos),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.description())
+        match *self {
+            ParseError::FnDuplicateParam(ref name) => write!(f, "{}: {}", self.description(), name),
+            _ if self.position().is_some() => write!(f, "{} at {}", self.description(), self.position().unwrap()),
+            _ => write!(f, "{}", self.description()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintWarning {
+    /// Statements follow an unconditional `return` or `break`. Carries the
+    /// name of the enclosing function, or `None` at the top level.
+    UnreachableCode(Option<String>),
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LintWarning::UnreachableCode(Some(ref name)) => {
+                write!(f, "Unreachable code in function '{}'", name)
+            }
+            LintWarning::UnreachableCode(None) => write!(f, "Unreachable code"),
+        }
     }
 }
 
@@ -81,16 +136,27 @@ pub struct FnDef {
     pub body: Box<Stmt>,
 }
 
+/// A compiled script, from `Engine::compile`, which can be run many times
+/// with `Engine::eval_ast` without parsing it again.
+#[derive(Debug, Clone)]
+pub struct AST(pub(crate) Vec<Stmt>, pub(crate) Vec<FnDef>);
+
 #[derive(Debug, Clone)]
 pub enum Stmt {
     If(Box<Expr>, Box<Stmt>),
     IfElse(Box<Expr>, Box<Stmt>, Box<Stmt>),
     While(Box<Expr>, Box<Stmt>),
     Loop(Box<Stmt>),
-    Var(String, Option<Box<Expr>>),
+    /// `for name in start..end step by { body }`, without `step` counting by 1
+    ForRange(String, Box<Expr>, Box<Expr>, Option<Box<Expr>>, Box<Stmt>),
+    /// `for name in array { body }`
+    For(String, Box<Expr>, Box<Stmt>),
+    /// `let name: type = value`, where the type and the value are optional
+    Var(String, Option<String>, Option<Box<Expr>>),
     Block(Vec<Stmt>),
     Expr(Box<Expr>),
-    Break,
+    Break(Option<Box<Expr>>),
+    Continue,
     Return,
     ReturnWithVal(Box<Expr>),
 }
@@ -99,20 +165,24 @@ pub enum Stmt {
 pub enum Expr {
     IntConst(i64),
     FloatConst(f64),
-    Identifier(String),
+    Identifier(String, Position),
     CharConst(char),
     StringConst(String),
-    FnCall(String, Vec<Expr>),
+    FnCall(String, Vec<Expr>, Position),
     Assignment(Box<Expr>, Box<Expr>),
     Dot(Box<Expr>, Box<Expr>),
-    Index(String, Box<Expr>),
+    Index(String, Box<Expr>, Position),
     Array(Vec<Expr>),
+    Block(Vec<Stmt>),
+    /// `guard ? then : otherwise`
+    Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
+    Sequence(Vec<Expr>),
     True,
     False,
     Unit,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Token {
     IntConst(i64),
     FloatConst(f64),
@@ -133,6 +203,7 @@ pub enum Token {
     Divide,
     Semicolon,
     Colon,
+    Question,
     Comma,
     Period,
     Equals,
@@ -143,9 +214,13 @@ pub enum Token {
     Else,
     While,
     Loop,
+    For,
+    In,
+    Range,
     LessThan,
     GreaterThan,
     Bang,
+    BitwiseNot,
     LessThanEqual,
     GreaterThanEqual,
     EqualTo,
@@ -156,6 +231,7 @@ pub enum Token {
     And,
     Fn,
     Break,
+    Continue,
     Return,
     PlusAssign,
     MinusAssign,
@@ -196,12 +272,14 @@ impl Token {
             Multiply         |
             Divide           |
             Colon            |
+            Question         |
             Comma            |
             Period           |
             Equals           |
             LessThan         |
             GreaterThan      |
             Bang             |
+            BitwiseNot       |
             LessThanEqual    |
             GreaterThanEqual |
             EqualTo          |
@@ -210,8 +288,12 @@ impl Token {
             Or               |
             Ampersand        |
             And              |
+            Semicolon        | // a statement can't start with a binary operator
+            LexErr(LexError::Nothing) | // nor can the script
             If               |
             While            |
+            In               |
+            Range            |
             PlusAssign       |
             MinusAssign      |
             MultiplyAssign   |
@@ -278,12 +360,200 @@ impl Token {
     }
 }
 
+/// The location of a token in the script, as byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A place in a script, counting lines and columns (in chars) from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// The position of an error which didn't come from a place in a script,
+    /// such as Rust code calling a function that doesn't exist.
+    pub fn none() -> Position {
+        Position { line: 0, column: 0 }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.line == 0
+    }
+
+    /// Find the line and column of the byte `offset` into `src`, e.g. the
+    /// start of a `Span`. An offset past the end counts as the end, and one
+    /// inside a char as the start of that char.
+    pub fn from_offset(src: &str, offset: usize) -> Position {
+        let mut offset = offset.min(src.len());
+        while !src.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let before = &src[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+        Position {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// The line of `src` this position is on, with a caret under the column,
+    /// the way rustc points at errors. `None` if `src` has no such line, as
+    /// for `Position::none()`.
+    pub fn snippet(&self, src: &str) -> Option<String> {
+        if self.is_none() {
+            return None;
+        }
+
+        let line = src.lines().nth(self.line - 1)?;
+        let gutter = self.line.to_string();
+        let indent: String = line.chars()
+            .take(self.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        Some(format!("{} | {}\n{} | {}^", gutter, line, " ".repeat(gutter.len()), indent))
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, col {}", self.line, self.column)
+    }
+}
+
+/// A peekable stream of characters which keeps track of how far into the
+/// input it has got.
+struct CharStream<'a> {
+    chars: Chars<'a>,
+    peeked: Option<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+    /// The line and column of the char `next` last gave
+    last: Position,
+}
+
+impl<'a> CharStream<'a> {
+    fn new(input: &'a str, offset: usize) -> CharStream<'a> {
+        CharStream {
+            chars: input.chars(),
+            peeked: None,
+            pos: offset,
+            line: 1,
+            column: 1,
+            last: Position { line: 1, column: 1 },
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peeked.take().or_else(|| self.chars.next());
+        if let Some(c) = c {
+            self.pos += c.len_utf8();
+            self.last = Position { line: self.line, column: self.column };
+
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        c
+    }
+
+    fn peek(&mut self) -> Option<&char> {
+        if self.peeked.is_none() {
+            self.peeked = self.chars.next();
+        }
+        self.peeked.as_ref()
+    }
+
+    /// The char after the one `peek` gives
+    fn peek_second(&mut self) -> Option<char> {
+        self.peek();
+        self.chars.clone().next()
+    }
+}
+
 pub struct TokenIterator<'a> {
     last: Token,
-    char_stream: Peekable<Chars<'a>>,
+    char_stream: CharStream<'a>,
+    token_start: usize,
+    token_pos: Position,
 }
 
 impl<'a> TokenIterator<'a> {
+    /// Reads exactly `digits` hex digits, as in `\x41` or `\u00e9`.
+    fn parse_hex_escape(&mut self, digits: usize) -> Result<char, LexError> {
+        let mut out_val: u32 = 0;
+
+        for _ in 0..digits {
+            let d = self.char_stream.next()
+                .and_then(|c| c.to_digit(16))
+                .ok_or(LexError::MalformedEscapeSequence)?;
+            out_val = out_val * 16 + d;
+        }
+
+        char::from_u32(out_val).ok_or(LexError::MalformedEscapeSequence)
+    }
+
+    /// Reads the `1F600}` of `\u{1F600}`: one to six hex digits and the closing
+    /// brace. Surrogates and values past `10FFFF` aren't chars, so they're an error.
+    fn parse_braced_escape(&mut self) -> Result<char, LexError> {
+        let mut out_val: u32 = 0;
+        let mut digits = 0;
+
+        loop {
+            match self.char_stream.next() {
+                Some('}') if digits > 0 => break,
+                Some(c) if digits < 6 => {
+                    out_val = out_val * 16 + c.to_digit(16).ok_or(LexError::MalformedEscapeSequence)?;
+                    digits += 1;
+                }
+                _ => return Err(LexError::MalformedEscapeSequence),
+            }
+        }
+
+        char::from_u32(out_val).ok_or(LexError::MalformedEscapeSequence)
+    }
+
+    /// Reads the `e5`, `E+5` or `e-5` at the end of a float. The exponent
+    /// needs at least one digit, so `1e` and `1e+` are an error.
+    fn parse_exponent(&mut self) -> Result<String, LexError> {
+        let mut out = String::new();
+        out.extend(self.char_stream.next());
+
+        if let Some(&sign) = self.char_stream.peek() {
+            if sign == '+' || sign == '-' {
+                out.push(sign);
+                self.char_stream.next();
+            }
+        }
+
+        let mut digits = 0;
+        while let Some(&d) = self.char_stream.peek() {
+            if !d.is_digit(10) {
+                break;
+            }
+            out.push(d);
+            digits += 1;
+            self.char_stream.next();
+        }
+
+        if digits == 0 {
+            return Err(LexError::MalformedNumber);
+        }
+
+        Ok(out)
+    }
+
     pub fn parse_string_const(&mut self, enclosing_char: char) -> Result<String, LexError> {
         let mut result = Vec::new();
         let mut escape = false;
@@ -309,71 +579,26 @@ impl<'a> TokenIterator<'a> {
                 }
                 'x' if escape => {
                     escape = false;
-                    let mut out_val: u32 = 0;
-                    for _ in 0..2 {
-                        if let Some(c) = self.char_stream.next() {
-                            if let Some(d1) = c.to_digit(16) {
-                                out_val *= 16;
-                                out_val += d1;
-                            } else {
-                                return Err(LexError::MalformedEscapeSequence);
-                            }
-                        } else {
-                            return Err(LexError::MalformedEscapeSequence);
-                        }
-                    }
-
-                    if let Some(r) = char::from_u32(out_val) {
-                        result.push(r);
-                    } else {
-                        return Err(LexError::MalformedEscapeSequence);
-                    }
+                    result.push(self.parse_hex_escape(2)?);
                 }
                 'u' if escape => {
                     escape = false;
-                    let mut out_val: u32 = 0;
-                    for _ in 0..4 {
-                        if let Some(c) = self.char_stream.next() {
-                            if let Some(d1) = c.to_digit(16) {
-                                out_val *= 16;
-                                out_val += d1;
-                            } else {
-                                return Err(LexError::MalformedEscapeSequence);
-                            }
-                        } else {
-                            return Err(LexError::MalformedEscapeSequence);
-                        }
-                    }
 
-                    if let Some(r) = char::from_u32(out_val) {
-                        result.push(r);
+                    if let Some(&'{') = self.char_stream.peek() {
+                        self.char_stream.next();
+                        result.push(self.parse_braced_escape()?);
                     } else {
-                        return Err(LexError::MalformedEscapeSequence);
+                        result.push(self.parse_hex_escape(4)?);
                     }
                 }
                 'U' if escape => {
                     escape = false;
-                    let mut out_val: u32 = 0;
-                    for _ in 0..8 {
-                        if let Some(c) = self.char_stream.next() {
-                            if let Some(d1) = c.to_digit(16) {
-                                out_val *= 16;
-                                out_val += d1;
-                            } else {
-                                return Err(LexError::MalformedEscapeSequence);
-                            }
-                        } else {
-                            return Err(LexError::MalformedEscapeSequence);
-                        }
-                    }
-
-                    if let Some(r) = char::from_u32(out_val) {
-                        result.push(r);
-                    } else {
-                        return Err(LexError::MalformedEscapeSequence);
-                    }
+                    result.push(self.parse_hex_escape(8)?);
+                }
+                x if enclosing_char == x && escape => {
+                    escape = false;
+                    result.push(x);
                 }
-                x if enclosing_char == x && escape => result.push(x),
                 x if enclosing_char == x && !escape => break,
                 _ if escape => return Err(LexError::MalformedEscapeSequence),
                 _ => {
@@ -389,6 +614,9 @@ impl<'a> TokenIterator<'a> {
 
     fn inner_next(&mut self) -> Option<Token> {
         while let Some(c) = self.char_stream.next() {
+            self.token_start = self.char_stream.pos - c.len_utf8();
+            self.token_pos = self.char_stream.last;
+
             match c {
                 '0'...'9' => {
                     let mut result = Vec::new();
@@ -401,7 +629,8 @@ impl<'a> TokenIterator<'a> {
                                 result.push(nxt);
                                 self.char_stream.next();
                             }
-                            '.' => {
+                            // Not the `..` of a range like `0..10`
+                            '.' if self.char_stream.peek_second() != Some('.') => {
                                 result.push(nxt);
                                 self.char_stream.next();
                                 while let Some(&nxt_float) = self.char_stream.peek() {
@@ -456,6 +685,14 @@ impl<'a> TokenIterator<'a> {
                                 }
                                 radix_base = Some(2);
                             }
+                            // Only right after a digit, so `1.exp()` still calls a method
+                            'e' | 'E' if radix_base.is_none() && result.last().map_or(false, |d| d.is_digit(10)) => {
+                                match self.parse_exponent() {
+                                    Ok(exponent) => result.extend(exponent.chars()),
+                                    Err(err) => return Some(Token::LexErr(err)),
+                                }
+                                break;
+                            }
                             _ => break,
                         }
                     }
@@ -499,12 +736,30 @@ impl<'a> TokenIterator<'a> {
                         "else" => return Some(Token::Else),
                         "while" => return Some(Token::While),
                         "loop" => return Some(Token::Loop),
+                        "for" => return Some(Token::For),
+                        "in" => return Some(Token::In),
                         "break" => return Some(Token::Break),
+                        "continue" => return Some(Token::Continue),
                         "return" => return Some(Token::Return),
                         "fn" => return Some(Token::Fn),
                         x => return Some(Token::Identifier(x.to_string())),
                     }
                 }
+                // A name in backticks is an identifier even if it's a keyword,
+                // for reaching members like `obj.`type``
+                '`' => {
+                    let mut result = String::new();
+
+                    loop {
+                        match self.char_stream.next() {
+                            Some('`') if !result.is_empty() => return Some(Token::Identifier(result)),
+                            Some('`') | Some('\n') | None => {
+                                return Some(Token::LexErr(LexError::MalformedIdentifier))
+                            }
+                            Some(x) => result.push(x),
+                        }
+                    }
+                }
                 '"' => {
                     match self.parse_string_const('"') {
                         Ok(out) => return Some(Token::StringConst(out)),
@@ -517,7 +772,6 @@ impl<'a> TokenIterator<'a> {
                             let mut chars = result.chars();
 
                             if let Some(out) = chars.next() {
-                                println!("result: {}", result);
                                 if chars.count() != 0 {
                                     return Some(Token::LexErr(LexError::MalformedChar));
                                 }
@@ -572,16 +826,20 @@ impl<'a> TokenIterator<'a> {
                                 if c == '\n' { break; }
                             }
                         }
+                        // Block comments nest, and as in Rust quotes mean nothing
+                        // inside them, so `/* "*/" */` ends at the first `*/`
                         Some(&'*') => {
                             let mut level = 1;
                             self.char_stream.next();
                             while let Some(c) = self.char_stream.next() {
-                                match c {
-                                    '/' => if let Some('*') = self.char_stream.next() {
-                                        level+=1;
+                                match (c, self.char_stream.peek()) {
+                                    ('/', Some(&'*')) => {
+                                        self.char_stream.next();
+                                        level += 1;
                                     }
-                                    '*' => if let Some('/') = self.char_stream.next() {
-                                        level-=1;
+                                    ('*', Some(&'/')) => {
+                                        self.char_stream.next();
+                                        level -= 1;
                                     }
                                     _ => (),
                                 }
@@ -590,6 +848,10 @@ impl<'a> TokenIterator<'a> {
                                     break;
                                 }
                             }
+
+                            if level > 0 {
+                                return Some(Token::LexErr(LexError::UnterminatedComment));
+                            }
                         }
                         Some(&'=') => {
                             self.char_stream.next();
@@ -600,8 +862,38 @@ impl<'a> TokenIterator<'a> {
                 }
                 ';' => return Some(Token::Semicolon),
                 ':' => return Some(Token::Colon),
+                '?' => return Some(Token::Question),
                 ',' => return Some(Token::Comma),
-                '.' => return Some(Token::Period),
+                '.' => {
+                    return match self.char_stream.peek() {
+                        Some(&'.') => {
+                            self.char_stream.next();
+                            Some(Token::Range)
+                        }
+                        // A float without the leading zero, like `.5`
+                        Some(&d) if d.is_digit(10) => {
+                            let mut out = "0.".to_string();
+
+                            while let Some(&d) = self.char_stream.peek() {
+                                if !d.is_digit(10) {
+                                    break;
+                                }
+                                out.push(d);
+                                self.char_stream.next();
+                            }
+
+                            if let Some(&'e') | Some(&'E') = self.char_stream.peek() {
+                                match self.parse_exponent() {
+                                    Ok(exponent) => out.push_str(&exponent),
+                                    Err(err) => return Some(Token::LexErr(err)),
+                                }
+                            }
+
+                            Some(out.parse().map(Token::FloatConst).unwrap_or(Token::LexErr(LexError::MalformedNumber)))
+                        }
+                        _ => Some(Token::Period),
+                    }
+                }
                 '=' => {
                     match self.char_stream.peek() {
                         Some(&'=') => {
@@ -714,6 +1006,8 @@ impl<'a> TokenIterator<'a> {
                             self.char_stream.next();
                             return Some(Token::PowerOfAssign);
                         }
+                        // `~` before an operand is bitwise not, between two it's a power
+                        _ if self.last.is_next_unary() => return Some(Token::BitwiseNot),
                         _ => return Some(Token::PowerOf)
                     }
                 },
@@ -739,8 +1033,116 @@ impl<'a> Iterator for TokenIterator<'a> {
     }
 }
 
-pub fn lex(input: &str) -> TokenIterator {
-    TokenIterator { last: Token::LexErr(LexError::Nothing), char_stream: input.chars().peekable() }
+/// The tokens of a script as the parser reads them: with one token of
+/// lookahead, and knowing where the last token it took starts.
+pub struct TokenStream<'a> {
+    tokens: TokenIterator<'a>,
+    peeked: Option<Option<(Token, Position)>>,
+    pos: Position,
+    /// How many levels of nesting the parser is in, see `MAX_EXPR_DEPTH`
+    depth: usize,
+}
+
+impl<'a> TokenStream<'a> {
+    pub fn peek(&mut self) -> Option<&Token> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.read());
+        }
+
+        match self.peeked {
+            Some(Some((ref token, _))) => Some(token),
+            _ => None,
+        }
+    }
+
+    /// Where the token `next` last gave starts
+    pub fn token_pos(&self) -> Position {
+        self.pos
+    }
+
+    /// Parse with `f` one level of nesting deeper, or fail with
+    /// `ExprTooDeep` if that's deeper than `MAX_EXPR_DEPTH`
+    fn nested<T, F>(&mut self, f: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, ParseError>,
+    {
+        if self.depth >= MAX_EXPR_DEPTH {
+            return Err(ParseError::ExprTooDeep);
+        }
+
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+
+        result
+    }
+
+    /// The position of the token `peek` gives, or of the last token at the
+    /// end of the input
+    pub fn peek_pos(&mut self) -> Position {
+        self.peek();
+
+        match self.peeked {
+            Some(Some((_, pos))) => pos,
+            _ => self.pos,
+        }
+    }
+
+    fn read(&mut self) -> Option<(Token, Position)> {
+        let token = self.tokens.next();
+        token.map(|token| (token, self.tokens.token_pos))
+    }
+}
+
+impl<'a> Iterator for TokenStream<'a> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        let next = match self.peeked.take() {
+            Some(next) => next,
+            None => self.read(),
+        };
+
+        next.map(|(token, pos)| {
+            self.pos = pos;
+            token
+        })
+    }
+}
+
+pub fn lex(input: &str) -> TokenStream {
+    TokenStream { tokens: lex_at(input, 0), peeked: None, pos: Position { line: 1, column: 1 }, depth: 0 }
+}
+
+fn lex_at(input: &str, offset: usize) -> TokenIterator {
+    TokenIterator {
+        last: Token::LexErr(LexError::Nothing),
+        char_stream: CharStream::new(input, offset),
+        token_start: offset,
+        token_pos: Position { line: 1, column: 1 },
+    }
+}
+
+/// Splits a script into tokens without parsing it, along with where each
+/// token is in `input`. Characters which can't be lexed come out as
+/// `Token::LexErr` rather than stopping the stream.
+pub fn tokenize(input: &str) -> Vec<(Token, Span)> {
+    tokenize_at(input, 0)
+}
+
+/// Like `tokenize`, for a piece of a larger script which starts `offset`
+/// bytes into it. The spans are counted from the start of the whole script,
+/// so an editor can relex just the region that changed.
+pub fn tokenize_at(input: &str, offset: usize) -> Vec<(Token, Span)> {
+    let mut tokens = lex_at(input, offset);
+    let mut result = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        let span = Span { start: tokens.token_start, end: tokens.char_stream.pos };
+        result.push((token, span));
+    }
+
+    result
 }
 
 fn get_precedence(token: &Token) -> i32 {
@@ -756,7 +1158,8 @@ fn get_precedence(token: &Token) -> i32 {
         | Token::OrAssign
         | Token::XOrAssign
         | Token::ModuloAssign
-        | Token::PowerOfAssign => 10,
+        | Token::PowerOfAssign
+        | Token::Question => 10,
         Token::Or
         | Token::XOr
         | Token::Pipe  => 11,
@@ -781,7 +1184,7 @@ fn get_precedence(token: &Token) -> i32 {
     }
 }
 
-fn parse_paren_expr<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Expr, ParseError> {
+fn parse_paren_expr<'a>(input: &mut TokenStream<'a>) -> Result<Expr, ParseError> {
     let expr = try!(parse_expr(input));
 
     match input.next() {
@@ -791,13 +1194,14 @@ fn parse_paren_expr<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Expr,
 }
 
 fn parse_call_expr<'a>(id: String,
-                       input: &mut Peekable<TokenIterator<'a>>)
+                       pos: Position,
+                       input: &mut TokenStream<'a>)
                        -> Result<Expr, ParseError> {
     let mut args = Vec::new();
 
     if let Some(&Token::RParen) = input.peek() {
         input.next();
-        return Ok(Expr::FnCall(id, args));
+        return Ok(Expr::FnCall(id, args, pos));
     }
 
     loop {
@@ -810,7 +1214,7 @@ fn parse_call_expr<'a>(id: String,
         match input.peek() {
             Some(&Token::RParen) => {
                 input.next();
-                return Ok(Expr::FnCall(id, args));
+                return Ok(Expr::FnCall(id, args, pos));
             }
             Some(&Token::Comma) => (),
             _ => return Err(ParseError::MalformedCallExpr),
@@ -821,13 +1225,14 @@ fn parse_call_expr<'a>(id: String,
 }
 
 fn parse_index_expr<'a>(id: String,
-                        input: &mut Peekable<TokenIterator<'a>>)
+                        pos: Position,
+                        input: &mut TokenStream<'a>)
                         -> Result<Expr, ParseError> {
     if let Ok(idx) = parse_expr(input) {
         match input.peek() {
             Some(&Token::RSquare) => {
                 input.next();
-                return Ok(Expr::Index(id, Box::new(idx)));
+                return Ok(Expr::Index(id, Box::new(idx), pos));
             }
             _ => return Err(ParseError::MalformedIndexExpr),
         }
@@ -837,22 +1242,23 @@ fn parse_index_expr<'a>(id: String,
 }
 
 fn parse_ident_expr<'a>(id: String,
-                        input: &mut Peekable<TokenIterator<'a>>)
+                        pos: Position,
+                        input: &mut TokenStream<'a>)
                         -> Result<Expr, ParseError> {
     match input.peek() {
         Some(&Token::LParen) => {
             input.next();
-            parse_call_expr(id, input)
+            parse_call_expr(id, pos, input)
         }
         Some(&Token::LSquare) => {
             input.next();
-            parse_index_expr(id, input)
+            parse_index_expr(id, pos, input)
         }
-        _ => Ok(Expr::Identifier(id)),
+        _ => Ok(Expr::Identifier(id, pos)),
     }
 }
 
-fn parse_array_expr<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Expr, ParseError> {
+fn parse_array_expr<'a>(input: &mut TokenStream<'a>) -> Result<Expr, ParseError> {
     let mut arr = Vec::new();
 
     let skip_contents = match input.peek() {
@@ -881,18 +1287,29 @@ fn parse_array_expr<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Expr,
 
 }
 
-fn parse_primary<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Expr, ParseError> {
+fn parse_primary<'a>(input: &mut TokenStream<'a>) -> Result<Expr, ParseError> {
+    // A loop used as a value gives what it was broken with, like a block
+    // gives the value of its last statement
+    match input.peek() {
+        Some(&Token::While) => return parse_while(input).map(|s| Expr::Block(vec![s])),
+        Some(&Token::Loop) => return parse_loop(input).map(|s| Expr::Block(vec![s])),
+        Some(&Token::For) => return parse_for(input).map(|s| Expr::Block(vec![s])),
+        _ => (),
+    }
+
     if let Some(token) = input.next() {
         match token {
             Token::IntConst(ref x) => Ok(Expr::IntConst(*x)),
             Token::FloatConst(ref x) => Ok(Expr::FloatConst(*x)),
             Token::StringConst(ref s) => Ok(Expr::StringConst(s.clone())),
             Token::CharConst(ref c) => Ok(Expr::CharConst(*c)),
-            Token::Identifier(ref s) => parse_ident_expr(s.clone(), input),
+            Token::Identifier(ref s) => parse_ident_expr(s.clone(), input.token_pos(), input),
             Token::LParen => parse_paren_expr(input),
             Token::LSquare => parse_array_expr(input),
+            Token::LCurly => parse_block_contents(input).map(Expr::Block),
             Token::True => Ok(Expr::True),
             Token::False => Ok(Expr::False),
+            Token::LexErr(LexError::UnterminatedComment) => Err(ParseError::UnterminatedComment(input.token_pos())),
             Token::LexErr(le) => {
                 println!("Error: {}", le);
                 Err(ParseError::BadInput)
@@ -907,24 +1324,55 @@ fn parse_primary<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Expr, Pa
     }
 }
 
-fn parse_unary<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Expr, ParseError> {
+/// How deeply expressions and statements can be nested in a script, by
+/// brackets, blocks, unary operators or operators which bind right to left.
+/// The parser and the engine both recurse once per level, so without a limit
+/// a machine-generated script like `((((...))))` could overflow the stack.
+/// Operators which bind left to right, as in `1+1+1+...`, don't nest.
+pub const MAX_EXPR_DEPTH: usize = 128;
+
+fn parse_unary<'a>(input: &mut TokenStream<'a>) -> Result<Expr, ParseError> {
+    input.nested(parse_unary_operand)
+}
+
+fn parse_unary_operand<'a>(input: &mut TokenStream<'a>) -> Result<Expr, ParseError> {
     let tok = match input.peek() {
         Some(tok) => tok.clone(),
         None => return Err(ParseError::InputPastEndOfFile),
     };
 
     match tok {
-        Token::UnaryMinus => { input.next(); Ok(Expr::FnCall("-".to_string(), vec![parse_primary(input)?])) }
-        Token::UnaryPlus => { input.next(); parse_primary(input) }
-        Token::Bang => { input.next(); Ok(Expr::FnCall("!".to_string(), vec![parse_primary(input)?])) }
+        // A binary operator where an operand is expected can only be a sign,
+        // as after `step` in `for i in 10..0 step -1`
+        Token::UnaryMinus | Token::Minus => unary_call(input, "-"),
+        Token::UnaryPlus | Token::Plus => { input.next(); parse_unary(input) }
+        Token::Bang => unary_call(input, "!"),
+        Token::BitwiseNot => unary_call(input, "~"),
         _ => parse_primary(input)
     }
 }
 
-fn parse_binop<'a>(input: &mut Peekable<TokenIterator<'a>>,
+/// Takes the operator token and applies `op` to the operand after it
+fn unary_call<'a>(input: &mut TokenStream<'a>, op: &str) -> Result<Expr, ParseError> {
+    input.next();
+    let pos = input.token_pos();
+
+    Ok(Expr::FnCall(op.to_string(), vec![try!(parse_unary(input))], pos))
+}
+
+fn parse_binop<'a>(input: &mut TokenStream<'a>,
                    prec: i32,
                    lhs: Expr)
                    -> Result<Expr, ParseError> {
+    input.nested(|input| parse_binop_chain(input, prec, lhs))
+}
+
+// Operators of the same precedence which bind left to right are taken in a
+// loop, so only a change of precedence or a right-binding operator recurses
+fn parse_binop_chain<'a>(input: &mut TokenStream<'a>,
+                         prec: i32,
+                         lhs: Expr)
+                         -> Result<Expr, ParseError> {
     let mut lhs_curr = lhs;
 
     loop {
@@ -939,6 +1387,24 @@ fn parse_binop<'a>(input: &mut Peekable<TokenIterator<'a>>,
         }
 
         if let Some(op_token) = input.next() {
+            let pos = input.token_pos();
+
+            if op_token == Token::Question {
+                let then_expr = try!(parse_expr(input));
+
+                match input.next() {
+                    Some(Token::Colon) => (),
+                    _ => return Err(ParseError::MissingColon),
+                }
+
+                // Like assignments, `a ? b : c ? d : e` binds right to left
+                let else_expr = try!(parse_unary(input));
+                let else_expr = try!(parse_binop(input, curr_prec, else_expr));
+
+                lhs_curr = Expr::Ternary(Box::new(lhs_curr), Box::new(then_expr), Box::new(else_expr));
+                continue;
+            }
+
             let mut rhs = try!(parse_unary(input));
 
             let mut next_prec = -1;
@@ -947,7 +1413,10 @@ fn parse_binop<'a>(input: &mut Peekable<TokenIterator<'a>>,
                 next_prec = get_precedence(next_op);
             }
 
-            if curr_prec < next_prec {
+            if curr_prec == get_precedence(&Token::Equals) {
+                // Assignments bind right to left, so that `a = b = 0` assigns to `b` first
+                rhs = try!(parse_binop(input, curr_prec, rhs));
+            } else if curr_prec < next_prec {
                 rhs = try!(parse_binop(input, curr_prec + 1, rhs));
             } else if curr_prec >= 100 {
                 // Always bind right to left for precedence over 100
@@ -955,108 +1424,108 @@ fn parse_binop<'a>(input: &mut Peekable<TokenIterator<'a>>,
             }
 
             lhs_curr = match op_token {
-                Token::Plus => Expr::FnCall("+".to_string(), vec![lhs_curr, rhs]),
-                Token::Minus => Expr::FnCall("-".to_string(), vec![lhs_curr, rhs]),
-                Token::Multiply => Expr::FnCall("*".to_string(), vec![lhs_curr, rhs]),
-                Token::Divide => Expr::FnCall("/".to_string(), vec![lhs_curr, rhs]),
+                Token::Plus => Expr::FnCall("+".to_string(), vec![lhs_curr, rhs], pos),
+                Token::Minus => Expr::FnCall("-".to_string(), vec![lhs_curr, rhs], pos),
+                Token::Multiply => Expr::FnCall("*".to_string(), vec![lhs_curr, rhs], pos),
+                Token::Divide => Expr::FnCall("/".to_string(), vec![lhs_curr, rhs], pos),
                 Token::Equals => Expr::Assignment(Box::new(lhs_curr), Box::new(rhs)),
                 Token::PlusAssign  => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall("+".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall("+".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
                 Token::MinusAssign  => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall("-".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall("-".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
                 Token::Period => Expr::Dot(Box::new(lhs_curr), Box::new(rhs)),
-                Token::EqualTo => Expr::FnCall("==".to_string(), vec![lhs_curr, rhs]),
-                Token::NotEqualTo => Expr::FnCall("!=".to_string(), vec![lhs_curr, rhs]),
-                Token::LessThan => Expr::FnCall("<".to_string(), vec![lhs_curr, rhs]),
+                Token::EqualTo => Expr::FnCall("==".to_string(), vec![lhs_curr, rhs], pos),
+                Token::NotEqualTo => Expr::FnCall("!=".to_string(), vec![lhs_curr, rhs], pos),
+                Token::LessThan => Expr::FnCall("<".to_string(), vec![lhs_curr, rhs], pos),
                 Token::LessThanEqual => {
-                    Expr::FnCall("<=".to_string(), vec![lhs_curr, rhs])
+                    Expr::FnCall("<=".to_string(), vec![lhs_curr, rhs], pos)
                 }
-                Token::GreaterThan => Expr::FnCall(">".to_string(), vec![lhs_curr, rhs]),
+                Token::GreaterThan => Expr::FnCall(">".to_string(), vec![lhs_curr, rhs], pos),
                 Token::GreaterThanEqual => {
-                    Expr::FnCall(">=".to_string(), vec![lhs_curr, rhs])
+                    Expr::FnCall(">=".to_string(), vec![lhs_curr, rhs], pos)
                 }
-                Token::Or => Expr::FnCall("||".to_string(), vec![lhs_curr, rhs]),
-                Token::And => Expr::FnCall("&&".to_string(), vec![lhs_curr, rhs]),
-                Token::XOr => Expr::FnCall("^".to_string(), vec![lhs_curr, rhs]),
+                Token::Or => Expr::FnCall("||".to_string(), vec![lhs_curr, rhs], pos),
+                Token::And => Expr::FnCall("&&".to_string(), vec![lhs_curr, rhs], pos),
+                Token::XOr => Expr::FnCall("^".to_string(), vec![lhs_curr, rhs], pos),
                 Token::OrAssign => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall("|".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall("|".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
                 Token::AndAssign => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall("&".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall("&".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
                 Token::XOrAssign => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall("^".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall("^".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
                 Token::MultiplyAssign => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall("*".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall("*".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
                 Token::DivideAssign => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall("/".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall("/".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
                 Token::Pipe => {
-                    Expr::FnCall("|".to_string(), vec![lhs_curr, rhs])
+                    Expr::FnCall("|".to_string(), vec![lhs_curr, rhs], pos)
                 },
-                Token::LeftShift => Expr::FnCall("<<".to_string(), vec![lhs_curr, rhs]),
-                Token::RightShift => Expr::FnCall(">>".to_string(), vec![lhs_curr, rhs]),
+                Token::LeftShift => Expr::FnCall("<<".to_string(), vec![lhs_curr, rhs], pos),
+                Token::RightShift => Expr::FnCall(">>".to_string(), vec![lhs_curr, rhs], pos),
                 Token::LeftShiftAssign => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall("<<".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall("<<".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
                 Token::RightShiftAssign => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall(">>".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall(">>".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
-                Token::Ampersand => Expr::FnCall("&".to_string(), vec![lhs_curr, rhs]),
-                Token::Modulo => Expr::FnCall("%".to_string(), vec![lhs_curr, rhs]),
+                Token::Ampersand => Expr::FnCall("&".to_string(), vec![lhs_curr, rhs], pos),
+                Token::Modulo => Expr::FnCall("%".to_string(), vec![lhs_curr, rhs], pos),
                 Token::ModuloAssign => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall("%".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall("%".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
-                Token::PowerOf => Expr::FnCall("~".to_string(), vec![lhs_curr, rhs]),
+                Token::PowerOf => Expr::FnCall("~".to_string(), vec![lhs_curr, rhs], pos),
                 Token::PowerOfAssign => {
                     let lhs_copy = lhs_curr.clone();
                     Expr::Assignment(
                         Box::new(lhs_curr),
-                        Box::new(Expr::FnCall("~".to_string(), vec![lhs_copy, rhs]))
+                        Box::new(Expr::FnCall("~".to_string(), vec![lhs_copy, rhs], pos))
                     )
                 },
                 _ => return Err(ParseError::UnknownOperator),
@@ -1065,7 +1534,7 @@ fn parse_binop<'a>(input: &mut Peekable<TokenIterator<'a>>,
     }
 }
 
-fn parse_expr<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Expr, ParseError> {
+fn parse_expr<'a>(input: &mut TokenStream<'a>) -> Result<Expr, ParseError> {
     match input.peek() {
         Some(Token::RParen) => Ok(Expr::Unit),
         _ => {
@@ -1076,40 +1545,40 @@ fn parse_expr<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Expr, Parse
     }
 }
 
-fn parse_if<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, ParseError> {
+fn parse_if<'a>(input: &mut TokenStream<'a>) -> Result<Stmt, ParseError> {
     input.next();
 
     let guard = try!(parse_expr(input));
-    let body = try!(parse_block(input));
+    let body = try!(parse_body(input));
 
     match input.peek() {
         Some(&Token::Else) => {
             input.next();
-            let else_body = try!(parse_block(input));
+            let else_body = try!(parse_body(input));
             Ok(Stmt::IfElse(Box::new(guard), Box::new(body), Box::new(else_body)))
         }
         _ => Ok(Stmt::If(Box::new(guard), Box::new(body))),
     }
 }
 
-fn parse_while<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, ParseError> {
+fn parse_while<'a>(input: &mut TokenStream<'a>) -> Result<Stmt, ParseError> {
     input.next();
 
     let guard = try!(parse_expr(input));
-    let body = try!(parse_block(input));
+    let body = try!(parse_body(input));
 
     Ok(Stmt::While(Box::new(guard), Box::new(body)))
 }
 
-fn parse_loop<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, ParseError> {
+fn parse_loop<'a>(input: &mut TokenStream<'a>) -> Result<Stmt, ParseError> {
     input.next();
 
-    let body = try!(parse_block(input));
+    let body = try!(parse_body(input));
 
     Ok(Stmt::Loop(Box::new(body)))
 }
 
-fn parse_var<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, ParseError> {
+fn parse_for<'a>(input: &mut TokenStream<'a>) -> Result<Stmt, ParseError> {
     input.next();
 
     let name = match input.next() {
@@ -1117,17 +1586,94 @@ fn parse_var<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, ParseE
         _ => return Err(ParseError::VarExpectsIdentifier),
     };
 
+    match input.next() {
+        Some(Token::In) => (),
+        _ => return Err(ParseError::ForMissingIn),
+    }
+
+    let start = try!(parse_expr(input));
+
+    // Without `..` this loops over the elements of an array
+    match input.peek() {
+        Some(&Token::Range) => { input.next(); }
+        _ => {
+            let body = try!(parse_body(input));
+            return Ok(Stmt::For(name, Box::new(start), Box::new(body)));
+        }
+    }
+
+    let end = try!(parse_expr(input));
+
+    // `step` is only a keyword here, so it can still name variables
+    let step = match input.peek() {
+        Some(&Token::Identifier(ref s)) if s == "step" => true,
+        _ => false,
+    };
+
+    let step = if step {
+        input.next();
+        Some(Box::new(try!(parse_expr(input))))
+    } else {
+        None
+    };
+
+    let body = try!(parse_body(input));
+
+    Ok(Stmt::ForRange(name, Box::new(start), Box::new(end), step, Box::new(body)))
+}
+
+fn parse_var<'a>(input: &mut TokenStream<'a>) -> Result<Stmt, ParseError> {
+    input.next();
+
+    let name = match input.next() {
+        Some(Token::Identifier(ref s)) => s.clone(),
+        _ => return Err(ParseError::VarExpectsIdentifier),
+    };
+
+    // The type is checked against the initial value when the `let` runs
+    let var_type = match input.peek() {
+        Some(&Token::Colon) => {
+            input.next();
+
+            match input.next() {
+                Some(Token::Identifier(s)) => Some(s),
+                _ => return Err(ParseError::VarMissingType),
+            }
+        }
+        _ => None,
+    };
+
     match input.peek() {
         Some(&Token::Equals) => {
             input.next();
-            let initializer = try!(parse_expr(input));
-            Ok(Stmt::Var(name, Some(Box::new(initializer))))
+            let initializer = try!(parse_seq_expr(input));
+            Ok(Stmt::Var(name, var_type, Some(Box::new(initializer))))
         }
-        _ => Ok(Stmt::Var(name, None)),
+        _ if var_type.is_some() => Err(ParseError::VarTypeWithoutValue),
+        _ => Ok(Stmt::Var(name, None, None)),
     }
 }
 
-fn parse_block<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, ParseError> {
+// The body of an `if`, `else`, `while` or `loop`: either a block, or a single
+// statement with its own scope. The `;` ending a single statement belongs to
+// the body, so that an `else` can follow it: `if x y = 1; else y = 2;`
+fn parse_body<'a>(input: &mut TokenStream<'a>) -> Result<Stmt, ParseError> {
+    if let Some(&Token::LCurly) = input.peek() {
+        return parse_block(input);
+    }
+
+    let stmt = try!(parse_stmt(input));
+
+    if needs_semicolon(&stmt) {
+        if let Some(&Token::Semicolon) = input.peek() {
+            input.next();
+        }
+    }
+
+    Ok(Stmt::Block(vec![stmt]))
+}
+
+fn parse_block<'a>(input: &mut TokenStream<'a>) -> Result<Stmt, ParseError> {
     match input.peek() {
         Some(&Token::LCurly) => (),
         _ => return Err(ParseError::MissingLCurly),
@@ -1135,6 +1681,11 @@ fn parse_block<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, Pars
 
     input.next();
 
+    parse_block_contents(input).map(Stmt::Block)
+}
+
+// Parses the statements of a block whose opening '{' was already consumed.
+fn parse_block_contents<'a>(input: &mut TokenStream<'a>) -> Result<Vec<Stmt>, ParseError> {
     let mut stmts = Vec::new();
 
     let skip_body = match input.peek() {
@@ -1144,12 +1695,23 @@ fn parse_block<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, Pars
 
     if !skip_body {
         while let Some(_) = input.peek() {
-            stmts.push(try!(parse_stmt(input)));
+            let stmt = try!(parse_stmt(input));
 
-            if let Some(&Token::Semicolon) = input.peek() {
-                input.next();
+            match input.peek() {
+                Some(&Token::Semicolon) => {
+                    input.next();
+                }
+                Some(&Token::RCurly) | None => (),
+                Some(&Token::LexErr(LexError::UnterminatedComment)) => {
+                    return Err(ParseError::UnterminatedComment(input.peek_pos()))
+                }
+                Some(&Token::LexErr(_)) => return Err(ParseError::BadInput),
+                Some(_) if !needs_semicolon(&stmt) => (),
+                Some(_) => return Err(ParseError::MissingSemicolon(input.peek_pos())),
             }
 
+            stmts.push(stmt);
+
             if let Some(&Token::RCurly) = input.peek() { break }
         }
     }
@@ -1157,32 +1719,84 @@ fn parse_block<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, Pars
     match input.peek() {
         Some(&Token::RCurly) => {
             input.next();
-            Ok(Stmt::Block(stmts))
+            Ok(stmts)
         }
         _ => Err(ParseError::MissingRCurly),
     }
 }
 
-fn parse_expr_stmt<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, ParseError> {
-    let expr = try!(parse_expr(input));
+fn parse_expr_stmt<'a>(input: &mut TokenStream<'a>) -> Result<Stmt, ParseError> {
+    let expr = try!(parse_seq_expr(input));
     Ok(Stmt::Expr(Box::new(expr)))
 }
 
-fn parse_stmt<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, ParseError> {
+// `a(), b(), c` evaluates each expression in turn and gives the last. This is
+// only parsed where a whole expression stands on its own, so the commas of
+// argument lists and arrays aren't affected.
+fn parse_seq_expr<'a>(input: &mut TokenStream<'a>) -> Result<Expr, ParseError> {
+    let first = try!(parse_expr(input));
+
+    match input.peek() {
+        Some(&Token::Comma) => (),
+        _ => return Ok(first),
+    }
+
+    let mut exprs = vec![first];
+
+    while let Some(&Token::Comma) = input.peek() {
+        input.next();
+        exprs.push(try!(parse_expr(input)));
+    }
+
+    Ok(Expr::Sequence(exprs))
+}
+
+/// Statements must be separated by ';', except after statements which end
+/// in a block, like `if` and `while`. The last statement of a script or a
+/// block doesn't need one either.
+fn needs_semicolon(stmt: &Stmt) -> bool {
+    match *stmt {
+        Stmt::If(_, _)
+        | Stmt::IfElse(_, _, _)
+        | Stmt::While(_, _)
+        | Stmt::Loop(_)
+        | Stmt::ForRange(_, _, _, _, _)
+        | Stmt::For(_, _, _)
+        | Stmt::Block(_) => false,
+        _ => true,
+    }
+}
+
+fn parse_stmt<'a>(input: &mut TokenStream<'a>) -> Result<Stmt, ParseError> {
+    input.nested(parse_stmt_kind)
+}
+
+fn parse_stmt_kind<'a>(input: &mut TokenStream<'a>) -> Result<Stmt, ParseError> {
     match input.peek() {
         Some(&Token::If) => parse_if(input),
         Some(&Token::While) => parse_while(input),
         Some(&Token::Loop) => parse_loop(input),
+        Some(&Token::For) => parse_for(input),
         Some(&Token::Break) => {
             input.next();
-            Ok(Stmt::Break)
+            match input.peek() {
+                Some(&Token::Semicolon) | Some(&Token::RCurly) | None => Ok(Stmt::Break(None)),
+                _ => {
+                    let val = try!(parse_expr(input));
+                    Ok(Stmt::Break(Some(Box::new(val))))
+                }
+            }
+        }
+        Some(&Token::Continue) => {
+            input.next();
+            Ok(Stmt::Continue)
         }
         Some(&Token::Return) => {
             input.next();
             match input.peek() {
                 Some(&Token::Semicolon) => Ok(Stmt::Return),
                 _ => {
-                    let ret = try!(parse_expr(input));
+                    let ret = try!(parse_seq_expr(input));
                     Ok(Stmt::ReturnWithVal(Box::new(ret)))
                 }
             }
@@ -1193,7 +1807,7 @@ fn parse_stmt<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<Stmt, Parse
     }
 }
 
-fn parse_fn<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<FnDef, ParseError> {
+fn parse_fn<'a>(input: &mut TokenStream<'a>) -> Result<FnDef, ParseError> {
     input.next();
 
     let name = match input.next() {
@@ -1224,6 +1838,10 @@ fn parse_fn<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<FnDef, ParseE
                 Some(Token::RParen) => break,
                 Some(Token::Comma) => (),
                 Some(Token::Identifier(ref s)) => {
+                    // `_` discards its argument, so it can be used any number of times
+                    if s != "_" && params.contains(s) {
+                        return Err(ParseError::FnDuplicateParam(s.clone()));
+                    }
                     params.push(s.clone());
                 }
                 _ => return Err(ParseError::MalformedCallExpr),
@@ -1240,26 +1858,136 @@ fn parse_fn<'a>(input: &mut Peekable<TokenIterator<'a>>) -> Result<FnDef, ParseE
     })
 }
 
-fn parse_top_level<'a>(input: &mut Peekable<TokenIterator<'a>>)
+fn parse_top_level<'a>(input: &mut TokenStream<'a>)
                        -> Result<(Vec<Stmt>, Vec<FnDef>), ParseError> {
     let mut stmts = Vec::new();
     let mut fndefs = Vec::new();
 
     while let Some(_) = input.peek() {
-        match input.peek() {
-            Some(&Token::Fn) => fndefs.push(try!(parse_fn(input))),
-            _ => stmts.push(try!(parse_stmt(input))),
-        }
+        let needs_semicolon = match input.peek() {
+            // Nothing is open at the top level, so these can only be left over
+            Some(&Token::RParen) | Some(&Token::RSquare) | Some(&Token::RCurly) => {
+                return Err(ParseError::TrailingInput)
+            }
+            Some(&Token::Fn) => {
+                fndefs.push(try!(parse_fn(input)));
+                false
+            }
+            _ => {
+                let stmt = try!(parse_stmt(input));
+                let needs_semicolon = needs_semicolon(&stmt);
+                stmts.push(stmt);
+                needs_semicolon
+            }
+        };
 
-        if let Some(&Token::Semicolon) = input.peek() {
-            input.next();
+        match input.peek() {
+            Some(&Token::Semicolon) => {
+                input.next();
+            }
+            None => (),
+            Some(&Token::RParen) | Some(&Token::RSquare) | Some(&Token::RCurly) => {
+                return Err(ParseError::TrailingInput)
+            }
+            // Something that couldn't be lexed, like an unclosed comment,
+            // rather than a second statement
+            Some(&Token::LexErr(LexError::UnterminatedComment)) => {
+                return Err(ParseError::UnterminatedComment(input.peek_pos()))
+            }
+            Some(&Token::LexErr(_)) => return Err(ParseError::BadInput),
+            Some(_) if !needs_semicolon => (),
+            Some(_) => return Err(ParseError::MissingSemicolon(input.peek_pos())),
         }
     }
 
     Ok((stmts, fndefs))
 }
 
-pub fn parse<'a>(input: &mut Peekable<TokenIterator<'a>>)
+pub fn parse<'a>(input: &mut TokenStream<'a>)
                  -> Result<(Vec<Stmt>, Vec<FnDef>), ParseError> {
     parse_top_level(input)
 }
+
+/// Walks a parsed script looking for statements that can never run.
+pub fn lint(stmts: &[Stmt], fndefs: &[FnDef]) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    lint_block(stmts, &None, &mut warnings);
+
+    for f in fndefs {
+        lint_stmt(&f.body, &Some(f.name.clone()), &mut warnings);
+    }
+
+    warnings
+}
+
+fn lint_block(stmts: &[Stmt], context: &Option<String>, warnings: &mut Vec<LintWarning>) {
+    for (i, stmt) in stmts.iter().enumerate() {
+        lint_stmt(stmt, context, warnings);
+
+        match *stmt {
+            Stmt::Break(_) | Stmt::Continue | Stmt::Return | Stmt::ReturnWithVal(_) if i + 1 < stmts.len() => {
+                warnings.push(LintWarning::UnreachableCode(context.clone()));
+                return;
+            }
+            _ => (),
+        }
+    }
+}
+
+fn lint_stmt(stmt: &Stmt, context: &Option<String>, warnings: &mut Vec<LintWarning>) {
+    match *stmt {
+        Stmt::If(ref guard, ref body) | Stmt::While(ref guard, ref body) => {
+            lint_expr(guard, context, warnings);
+            lint_stmt(body, context, warnings);
+        }
+        Stmt::IfElse(ref guard, ref body, ref else_body) => {
+            lint_expr(guard, context, warnings);
+            lint_stmt(body, context, warnings);
+            lint_stmt(else_body, context, warnings);
+        }
+        Stmt::Loop(ref body) => lint_stmt(body, context, warnings),
+        Stmt::ForRange(_, ref start, ref end, ref step, ref body) => {
+            lint_expr(start, context, warnings);
+            lint_expr(end, context, warnings);
+            if let Some(ref step) = *step {
+                lint_expr(step, context, warnings);
+            }
+            lint_stmt(body, context, warnings);
+        }
+        Stmt::For(_, ref array, ref body) => {
+            lint_expr(array, context, warnings);
+            lint_stmt(body, context, warnings);
+        }
+        Stmt::Block(ref stmts) => lint_block(stmts, context, warnings),
+        Stmt::Var(_, _, Some(ref e))
+        | Stmt::Expr(ref e)
+        | Stmt::ReturnWithVal(ref e)
+        | Stmt::Break(Some(ref e)) => {
+            lint_expr(e, context, warnings)
+        }
+        _ => (),
+    }
+}
+
+fn lint_expr(expr: &Expr, context: &Option<String>, warnings: &mut Vec<LintWarning>) {
+    match *expr {
+        Expr::Block(ref stmts) => lint_block(stmts, context, warnings),
+        Expr::FnCall(_, ref exprs, _) | Expr::Array(ref exprs) | Expr::Sequence(ref exprs) => {
+            for e in exprs {
+                lint_expr(e, context, warnings);
+            }
+        }
+        Expr::Assignment(ref lhs, ref rhs) | Expr::Dot(ref lhs, ref rhs) => {
+            lint_expr(lhs, context, warnings);
+            lint_expr(rhs, context, warnings);
+        }
+        Expr::Index(_, ref idx, _) => lint_expr(idx, context, warnings),
+        Expr::Ternary(ref guard, ref then_expr, ref else_expr) => {
+            lint_expr(guard, context, warnings);
+            lint_expr(then_expr, context, warnings);
+            lint_expr(else_expr, context, warnings);
+        }
+        _ => (),
+    }
+}
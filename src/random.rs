@@ -0,0 +1,77 @@
+//! Helper module which defines the random number functions
+//! of the `rand` feature.
+
+use std::any::TypeId;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use any::Any;
+use engine::{Engine, EvalAltResult};
+
+/// A seed which differs from run to run, for engines which scripts and hosts
+/// never call `srand` on.
+pub(crate) fn time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() ^ u64::from(d.subsec_nanos()) << 32)
+        .unwrap_or(0)
+}
+
+impl Engine {
+    /// Seed the generator behind `rand` and `rand_int`, so the numbers a
+    /// script gets can be reproduced. Scripts can do the same with `srand`.
+    pub fn seed_rng(&self, seed: u64) {
+        self.rng.set(seed);
+    }
+
+    // SplitMix64: tiny, fast, and good enough for games and simulations,
+    // but not for anything security related
+    fn next_random(&self) -> u64 {
+        let mut z = self.rng.get().wrapping_add(0x9E37_79B9_7F4A_7C15);
+        self.rng.set(z);
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+// A float in `0.0..1.0`, from the top 53 bits so every value is equally likely
+fn rand(engine: &Engine, _: Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult> {
+    Ok(Box::new((engine.next_random() >> 11) as f64 / (1u64 << 53) as f64))
+}
+
+// An integer from `lo` to `hi`, both included
+fn rand_int(engine: &Engine, args: Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult> {
+    let (lo, hi) = match (args[0].downcast_ref::<i64>(), args[1].downcast_ref::<i64>()) {
+        (Some(&lo), Some(&hi)) => (lo, hi),
+        _ => return Err(EvalAltResult::ErrorFunctionArgMismatch),
+    };
+
+    if lo > hi {
+        return Err(EvalAltResult::ErrorRuntime(format!("Cannot pick a number from {} to {}", lo, hi)));
+    }
+
+    // Wraps to 0 when the range covers every i64
+    let span = (hi.wrapping_sub(lo) as u64).wrapping_add(1);
+    let offset = if span == 0 { engine.next_random() } else { engine.next_random() % span };
+
+    Ok(Box::new(lo.wrapping_add(offset as i64)))
+}
+
+fn srand(engine: &Engine, args: Vec<&mut Any>) -> Result<Box<Any>, EvalAltResult> {
+    match args[0].downcast_ref::<i64>() {
+        Some(&seed) => {
+            engine.seed_rng(seed as u64);
+            Ok(Box::new(()))
+        }
+        None => Err(EvalAltResult::ErrorFunctionArgMismatch),
+    }
+}
+
+pub(crate) fn register_rand(engine: &mut Engine) {
+    let int = TypeId::of::<i64>();
+
+    engine.register_fn_with_engine("rand", Some(vec![]), Box::new(rand));
+    engine.register_fn_with_engine("rand_int", Some(vec![int, int]), Box::new(rand_int));
+    engine.register_fn_with_engine("srand", Some(vec![int]), Box::new(srand));
+}
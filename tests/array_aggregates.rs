@@ -0,0 +1,27 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult};
+
+#[test]
+fn test_array_sum() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("sum([1, 2, 3, 4])"), Ok(10));
+    assert_eq!(engine.eval::<f64>("sum([1.5, 2.5])"), Ok(4.0));
+    assert_eq!(engine.eval::<i64>("sum([])"), Ok(0));
+}
+
+#[test]
+fn test_array_min_max() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("min([3, 1, 2])"), Ok(1));
+    assert_eq!(engine.eval::<i64>("max([3, 1, 2])"), Ok(3));
+    assert_eq!(engine.eval::<f64>("let x = [0.5, -1.5, 2.25]; min(x)"), Ok(-1.5));
+    assert_eq!(engine.eval::<f64>("let x = [0.5, -1.5, 2.25]; max(x)"), Ok(2.25));
+
+    match engine.eval::<i64>("min([])") {
+        Err(EvalAltResult::ErrorArithmetic(_)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
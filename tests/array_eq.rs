@@ -0,0 +1,31 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, Position};
+
+#[test]
+fn test_array_eq() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<bool>("[1, 2] == [1, 2]"), Ok(true));
+    assert_eq!(engine.eval::<bool>("[] == []"), Ok(true));
+    assert_eq!(engine.eval::<bool>("[\"a\", [true]] == [\"a\", [true]]"), Ok(true));
+    assert_eq!(engine.eval::<bool>("[1, 2] != [1, 2]"), Ok(false));
+}
+
+#[test]
+fn test_array_ne() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<bool>("[1, 2] == [1, 2, 3]"), Ok(false));
+    assert_eq!(engine.eval::<bool>("[1, 2] == [1, 3]"), Ok(false));
+    assert_eq!(engine.eval::<bool>("[1, 2] == [1, \"2\"]"), Ok(false));
+    assert_eq!(engine.eval::<bool>("[1, 2] != [2, 1]"), Ok(true));
+}
+
+#[test]
+fn test_array_eq_without_element_eq() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<bool>("['a'] == ['a']"),
+               Err(EvalAltResult::ErrorFunctionNotFound("== (char,char)".into(), Position { line: 1, column: 7 })));
+}
@@ -1,23 +1,15 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{Engine, EvalAltResult};
 use rhai::RegisterFn;
 
 #[test]
 fn test_arrays() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("let x = [1, 2, 3]; x[1]") {
-        assert_eq!(result, 2);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("let x = [1, 2, 3]; x[1]"), Ok(2));
 
-    if let Ok(result) = engine.eval::<i64>("let y = [1, 2, 3]; y[1] = 5; y[1]") {
-        assert_eq!(result, 5);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("let y = [1, 2, 3]; y[1] = 5; y[1]"), Ok(5));
 }
 
 #[test]
@@ -53,16 +45,107 @@ fn test_array_with_structs() {
     engine.register_fn("update", TestStruct::update);
     engine.register_fn("new_ts", TestStruct::new);
 
-    if let Ok(result) = engine.eval::<i64>("let a = [new_ts()]; a[0].x") {
-        assert_eq!(result, 1);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("let a = [new_ts()]; a[0].x"), Ok(1));
 
-    if let Ok(result) = engine.eval::<i64>("let a = [new_ts()]; a[0].x = 100; a[0].update(); \
-                                            a[0].x") {
-        assert_eq!(result, 1100);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("let a = [new_ts()]; a[0].x = 100; a[0].update(); a[0].x"),
+               Ok(1100));
+
+    assert_eq!(engine.eval::<i64>("let a = [new_ts()]; a[1].x"), Err(EvalAltResult::ErrorArrayBounds(1, 1)));
+    assert_eq!(engine.eval::<()>("let a = [new_ts()]; a[-1].x = 5"), Err(EvalAltResult::ErrorArrayBounds(-1, 1)));
+}
+
+#[test]
+fn test_array_copies() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let a = [1, 2]; let b = a; b[0] = 5; a[0]"), Ok(1));
+    assert_eq!(engine.eval::<i64>("let a = [1, 2]; let b = clone(a); b[0] = 5; a[0]"), Ok(1));
+    assert_eq!(engine.eval::<i64>("let a = [1, 2]; let b = a.clone(); b[1] = 5; a[1] + b[1]"),
+               Ok(7));
+    assert_eq!(engine.eval::<String>("let s = \"ab\"; let t = clone(s); t += \"c\"; s"),
+               Ok("ab".to_string()));
+}
+
+#[test]
+fn test_array_out_of_bounds() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = [1, 2, 3]; x[3]"), Err(EvalAltResult::ErrorArrayBounds(3, 3)));
+    assert_eq!(engine.eval::<i64>("let x = [1, 2, 3]; let i = 0 - 1; x[i]"),
+               Err(EvalAltResult::ErrorArrayBounds(-1, 3)));
+    assert_eq!(engine.eval::<()>("let x = [1, 2, 3]; x[3] = 4"), Err(EvalAltResult::ErrorArrayBounds(3, 3)));
+    assert_eq!(engine.eval::<()>("let x = []; x[0] = 4"), Err(EvalAltResult::ErrorArrayBounds(0, 0)));
+    assert_eq!(engine.eval::<i64>("let x = [1]; x[-9223372036854775807]"),
+               Err(EvalAltResult::ErrorArrayBounds(-9223372036854775807, 1)));
+    assert_eq!(engine.eval::<i64>("let x = [1, 2, 3]; x[2] = 4; x[2]"), Ok(4));
+
+    let err = engine.eval::<i64>("let x = [1, 2, 3]; x[5]").unwrap_err();
+    assert_eq!(err.to_string(), "Array index out of bounds: index 5 of an array of length 3");
+}
+
+#[test]
+fn test_array_index_type_errors() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = 5; x[0]"),
+               Err(EvalAltResult::ErrorIndexingType("integer".to_string())));
+    assert_eq!(engine.eval::<()>("let x = 5; x[0] = 1"),
+               Err(EvalAltResult::ErrorIndexingType("integer".to_string())));
+    assert_eq!(engine.eval::<i64>("let x = [1, 2]; x[\"a\"]"),
+               Err(EvalAltResult::ErrorIndexExpr("string".to_string())));
+    assert_eq!(engine.eval::<()>("let x = [1, 2]; x[true] = 1"),
+               Err(EvalAltResult::ErrorIndexExpr("boolean".to_string())));
+
+    let err = engine.eval::<i64>("let x = 5; x[0]").unwrap_err();
+    assert_eq!(err.to_string(), "Cannot index value of type: integer");
+}
+
+#[test]
+fn test_array_fill() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("len(new_array())"), Ok(0));
+    assert_eq!(engine.eval::<i64>("let n = 2; let a = fill(7, n * 2); a[3] + len(a)"), Ok(11));
+    assert_eq!(engine.eval::<bool>("fill(\"ab\", 2) == [\"ab\", \"ab\"]"), Ok(true));
+    assert_eq!(engine.eval::<i64>("len(fill(1.5, 0))"), Ok(0));
+
+    // Rows of a grid are copies, not the same array
+    assert_eq!(
+        engine.eval::<i64>("let grid = fill(fill(0, 3), 2); let row = grid[0]; row[1] = 5; grid[0] = row; let other = grid[1]; other[1]"),
+        Ok(0)
+    );
+
+    assert_eq!(
+        engine.eval::<i64>("len(fill(0, -1))"),
+        Err(EvalAltResult::ErrorRuntime("Cannot fill an array with -1 elements".into()))
+    );
+    assert_eq!(
+        engine.eval::<i64>("len(fill(0, 9223372036854775807))"),
+        Err(EvalAltResult::ErrorRuntime("Cannot fill an array with 9223372036854775807 elements".into()))
+    );
+
+    let mut engine = Engine::builder().max_array_size(3).build();
+
+    assert_eq!(engine.eval::<i64>("len(fill(0, 3))"), Ok(3));
+    assert_eq!(
+        engine.eval::<i64>("len(fill(0, 4))"),
+        Err(EvalAltResult::ErrorRuntime("Cannot fill an array with 4 elements".into()))
+    );
+}
+
+#[test]
+fn test_array_fill_custom_type() {
+    #[derive(Clone)]
+    struct Cell(i64);
+
+    let mut engine = Engine::new();
+
+    engine.register_fn("cell", Cell);
+    engine.register_fn("get", |c: &mut Cell| c.0);
+
+    assert!(engine.eval::<i64>("let a = fill(cell(3), 2); a[1].get()").is_err());
+
+    engine.register_fn("clone", |c: &mut Cell| c.clone());
+
+    assert_eq!(engine.eval::<i64>("let a = fill(cell(3), 2); a[1].get()"), Ok(3));
 }
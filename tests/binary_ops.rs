@@ -6,39 +6,15 @@ use rhai::Engine;
 fn test_binary_ops() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("10 % 4") {
-        assert_eq!(result, 2);
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval::<i64>("10 << 4") {
-        assert_eq!(result, 160);
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval::<i64>("10 >> 4") {
-        assert_eq!(result, 0);
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval::<i64>("10 & 4") {
-        assert_eq!(result, 0);
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval::<i64>("10 | 4") {
-        assert_eq!(result, 14);
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval::<i64>("10 ^ 4") {
-        assert_eq!(result, 14);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("10 % 4"), Ok(2));
+
+    assert_eq!(engine.eval::<i64>("10 << 4"), Ok(160));
+
+    assert_eq!(engine.eval::<i64>("10 >> 4"), Ok(0));
+
+    assert_eq!(engine.eval::<i64>("10 & 4"), Ok(0));
+
+    assert_eq!(engine.eval::<i64>("10 | 4"), Ok(14));
+
+    assert_eq!(engine.eval::<i64>("10 ^ 4"), Ok(14));
 }
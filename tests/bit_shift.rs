@@ -6,20 +6,12 @@ use rhai::Engine;
 fn test_left_shift() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("4 << 2") {
-        assert_eq!(result, 16);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("4 << 2"), Ok(16));
 }
 
 #[test]
 fn test_right_shift() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("9 >> 1") {
-        assert_eq!(result, 4);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("9 >> 1"), Ok(4));
 }
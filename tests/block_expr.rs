@@ -0,0 +1,50 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, Position};
+
+#[test]
+fn test_block_expr() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = { let t = 3; t * t }; x"), Ok(9));
+    assert_eq!(engine.eval::<i64>("let x = { 1 + 2 } * 2; x"), Ok(6));
+    assert_eq!(engine.eval::<()>("let x = { let t = 3; }; x"), Ok(()));
+}
+
+#[test]
+fn test_block_expr_scope() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let t = 1; let x = { let t = 5; t }; t + x"), Ok(6));
+    assert_eq!(engine.eval::<i64>("let x = { let t = 3; t }; t"),
+               Err(EvalAltResult::ErrorVariableNotFound("t".to_string(), Position { line: 1, column: 27 })));
+}
+
+#[test]
+fn test_block_value() {
+    let mut engine = Engine::new();
+
+    // An `if` takes the value of the branch which ran
+    assert_eq!(engine.eval::<i64>("if true { 1 } else { 2 }"), Ok(1));
+    assert_eq!(engine.eval::<i64>("if false { 1 } else { 2 }"), Ok(2));
+    assert_eq!(engine.eval::<i64>("let x = { if false { 1 } else { 2 } }; x"), Ok(2));
+    assert_eq!(engine.eval::<i64>("if false { 1 } else if true { let b = 2; b * 2 } else { 3 }"), Ok(4));
+
+    // Nested blocks pass the value of their last statement outwards
+    assert_eq!(engine.eval::<i64>("if true { { { 3 } } }"), Ok(3));
+    assert_eq!(engine.eval::<i64>("let x = { let a = 1; { let b = 2; a + b } }; x"), Ok(3));
+}
+
+#[test]
+fn test_block_value_unit() {
+    let mut engine = Engine::new();
+
+    // Blocks ending in a `let`, an assignment or a loop, empty blocks, and an
+    // `if` without `else` whose guard is false have no value
+    assert_eq!(engine.eval::<bool>("let v = { let a = 1; }; v == ()"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let a = 1; let v = { a = 2 }; v == ()"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let v = { while false {} }; v == ()"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let v = { {} }; v == ()"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let v = { if false { 1 } }; v == ()"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let v = { if true { let a = 1; } else { 2 } }; v == ()"), Ok(true));
+}
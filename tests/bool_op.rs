@@ -1,25 +1,52 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{Engine, RegisterFn};
 
 #[test]
 fn test_bool_op1() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<bool>("true && (false || true)") {
-        assert_eq!(result, true);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<bool>("true && (false || true)"), Ok(true));
 }
 
 #[test]
 fn test_bool_op2() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<bool>("false && (false || true)") {
-        assert_eq!(result, false);
-    } else {
-        assert!(false);
+    assert_eq!(engine.eval::<bool>("false && (false || true)"), Ok(false));
+}
+
+#[test]
+fn test_bool_op_bitwise_dispatch() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<bool>("let a = true; let c = false; a & c"), Ok(false));
+    assert_eq!(engine.eval::<bool>("let a = true; let c = false; a | c"), Ok(true));
+    assert_eq!(engine.eval::<i64>("let a = 6; let c = 3; a & c"), Ok(2));
+    assert_eq!(engine.eval::<i64>("let a = 6; let c = 3; a | c"), Ok(7));
+    assert_eq!(engine.eval::<bool>("let a = 6 & 3; let b = true & true; b | a == 0"), Ok(true));
+}
+
+#[test]
+fn test_bool_op_dispatch_ignores_registration_order() {
+    fn and(x: bool, y: bool) -> bool {
+        x && y
+    }
+
+    fn binary_and(x: i64, y: i64) -> i64 {
+        x & y
+    }
+
+    let mut bool_first = Engine::builder().without_default_lib().build();
+    bool_first.register_fn("&", and);
+    bool_first.register_fn("&", binary_and);
+
+    let mut int_first = Engine::builder().without_default_lib().build();
+    int_first.register_fn("&", binary_and);
+    int_first.register_fn("&", and);
+
+    for engine in &mut [bool_first, int_first] {
+        assert_eq!(engine.eval::<bool>("true & false"), Ok(false));
+        assert_eq!(engine.eval::<i64>("6 & 3"), Ok(2));
     }
 }
@@ -0,0 +1,25 @@
+extern crate rhai;
+
+use rhai::Engine;
+
+#[test]
+fn test_braceless_bodies() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = 0; while x < 5 x += 1; x"), Ok(5));
+    assert_eq!(engine.eval::<i64>("let x = 0; if x == 0 x = 7; x"), Ok(7));
+    assert_eq!(engine.eval::<i64>("let x = 1; if (x > 5) x = 7; else x = 9; x"), Ok(9));
+    assert_eq!(engine.eval::<i64>("let x = 0; loop if x < 3 x += 1; else break; x"), Ok(3));
+    assert_eq!(engine.eval::<i64>("let x = 0; if true let x = 5; x"), Ok(0));
+}
+
+#[test]
+fn test_dangling_else() {
+    let mut engine = Engine::new();
+
+    // The `else` belongs to the inner `if`
+    assert_eq!(engine.eval::<i64>("let r = 0; if false if true r = 1; else r = 2; r"), Ok(0));
+    assert_eq!(engine.eval::<i64>("let r = 0; if true if false r = 1; else r = 2; r"), Ok(2));
+    assert_eq!(engine.eval::<i64>("let r = 0; if false if true r = 1; else r = 2; else r = 3; r"),
+               Ok(3));
+}
@@ -0,0 +1,32 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, Position, RegisterFn, Scope, ScopeExt};
+
+#[test]
+fn test_call_by_name() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("triple", |x: i64| x * 3);
+
+    assert_eq!(engine.eval::<i64>("call(\"triple\", 4)"), Ok(12));
+    assert_eq!(engine.eval::<i64>("fn add(a, b) { a + b } call(\"add\", 40, 2)"), Ok(42));
+    assert_eq!(engine.eval::<i64>("fn answer() { 42 } let f = \"answer\"; call(f)"), Ok(42));
+    assert_eq!(engine.eval::<i64>("call(\"+\", 1, 2)"), Ok(3));
+    assert_eq!(engine.eval::<i64>("call(\"call\", \"triple\", 2)"), Ok(6));
+
+    assert_eq!(engine.eval::<i64>("call(\"nope\", 1)"),
+               Err(EvalAltResult::ErrorFunctionNotFound("nope (integer)".to_string(), Position { line: 1, column: 1 })));
+    assert_eq!(engine.eval::<i64>("call(1, 2)"), Err(EvalAltResult::ErrorFunctionArgMismatch));
+    assert_eq!(engine.eval::<i64>("call()"), Err(EvalAltResult::ErrorFunctionArgMismatch));
+}
+
+#[test]
+fn test_call_by_name_from_scope() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    engine.eval_with_scope::<()>(&mut scope, "fn on_hit(x) { x - 10 } fn on_heal(x) { x + 5 }").unwrap();
+
+    scope.push_value("callback", "on_heal".to_string());
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "call(callback, 100)"), Ok(105));
+}
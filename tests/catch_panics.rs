@@ -0,0 +1,24 @@
+#![cfg(feature = "catch_panics")]
+
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, RegisterFn};
+
+#[test]
+fn test_catch_panics() {
+    let mut engine = Engine::new();
+
+    fn explode(x: i64) -> i64 {
+        if x > 10 {
+            panic!("too big");
+        }
+        x
+    }
+
+    engine.register_fn("explode", explode);
+
+    assert_eq!(engine.eval::<i64>("explode(5)"), Ok(5));
+    assert_eq!(engine.eval::<i64>("explode(50)"),
+               Err(EvalAltResult::ErrorRuntimePanic("too big".to_string())));
+    assert_eq!(engine.eval::<i64>("explode(7)"), Ok(7));
+}
@@ -0,0 +1,43 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, Position, RegisterFn};
+
+#[test]
+fn test_chained_assignment() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let a = 1; let b = 2; a = b = 0; a + b"), Ok(0));
+    assert_eq!(engine.eval::<i64>("let a; let b; let c; a = b = c = 7; a + b + c"), Ok(21));
+    assert_eq!(engine.eval::<i64>("let a = [1, 2]; let b = 0; a[1] = b = 5; a[1] + b"), Ok(10));
+    assert_eq!(engine.eval::<i64>("let a = 1; let b = 1; a = b += 2; a"), Ok(3));
+
+    // Each variable gets its own copy
+    assert_eq!(engine.eval::<i64>("let a; let b; a = b = [1]; b[0] = 2; a[0]"), Ok(1));
+
+    assert_eq!(engine.eval::<i64>("let a = 1; a = b = 0; a"),
+               Err(EvalAltResult::ErrorVariableNotFound("b".to_string(), Position { line: 1, column: 16 })));
+}
+
+#[test]
+fn test_chained_assignment_members() {
+    #[derive(Clone)]
+    struct Point {
+        x: i64,
+    }
+
+    let mut engine = Engine::new();
+
+    engine.register_get_set("x", |p: &mut Point| p.x, |p: &mut Point, x: i64| p.x = x);
+    engine.register_fn("point", || Point { x: 0 });
+
+    assert_eq!(engine.eval::<i64>("let p = point(); let n; n = p.x = 4; n + p.x"), Ok(8));
+}
+
+#[test]
+fn test_assignment_statement_has_no_value() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<bool>("let a = 1; let v = { a = 2 }; v == ()"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let a = 1; let v = { a += 2 }; v == ()"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let a = 1; let v = { if true { a = 2 } }; v == ()"), Ok(true));
+}
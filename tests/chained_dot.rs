@@ -0,0 +1,79 @@
+#![allow(bare_trait_objects)]
+
+extern crate rhai;
+
+use rhai::{Any, Engine};
+use rhai::RegisterFn;
+
+#[derive(Clone)]
+struct TestChild {
+    name: String,
+    value: i64,
+}
+
+impl TestChild {
+    fn get_name(&mut self) -> String {
+        self.name.clone()
+    }
+
+    fn get_value(&mut self) -> i64 {
+        self.value
+    }
+
+    fn double(&mut self) -> i64 {
+        self.value * 2
+    }
+}
+
+#[derive(Clone)]
+struct TestParent {
+    child: TestChild,
+}
+
+impl TestParent {
+    fn get_child(&mut self) -> TestChild {
+        self.child.clone()
+    }
+
+    fn get_items(&mut self) -> Vec<Box<Any>> {
+        vec![Box::new(self.child.clone()), Box::new(TestChild { name: "second".into(), value: 7 })]
+    }
+
+    fn new() -> TestParent {
+        TestParent { child: TestChild { name: "first".into(), value: 42 } }
+    }
+}
+
+fn new_engine() -> Engine {
+    let mut engine = Engine::new();
+
+    engine.register_type::<TestChild>();
+    engine.register_type::<TestParent>();
+
+    engine.register_get("name", TestChild::get_name);
+    engine.register_get("value", TestChild::get_value);
+    engine.register_get("items", TestParent::get_items);
+    engine.register_fn("double", TestChild::double);
+    engine.register_fn("getChild", TestParent::get_child);
+    engine.register_fn("new_tp", TestParent::new);
+
+    engine
+}
+
+#[test]
+fn test_chained_method_then_property() {
+    let mut engine = new_engine();
+
+    assert_eq!(engine.eval::<String>("let obj = new_tp(); obj.getChild().name"),
+               Ok("first".to_string()));
+    assert_eq!(engine.eval::<i64>("let obj = new_tp(); obj.getChild().double()"), Ok(84));
+}
+
+#[test]
+fn test_chained_index_then_property() {
+    let mut engine = new_engine();
+
+    assert_eq!(engine.eval::<i64>("let obj = new_tp(); obj.items[0].value"), Ok(42));
+    assert_eq!(engine.eval::<String>("let obj = new_tp(); obj.items[1].name"),
+               Ok("second".to_string()));
+}
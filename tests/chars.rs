@@ -1,25 +1,50 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{tokenize, Engine, EvalAltResult, LexError, ParseError, Token};
 
 #[test]
 fn test_chars() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<char>("'y'") {
-        assert_eq!(result, 'y');
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<char>("'y'"), Ok('y'));
 
-    if let Ok(result) = engine.eval::<char>("'\\u2764'") {
-        assert_eq!(result, '❤');
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<char>("'\\u2764'"), Ok('❤'));
 
     match engine.eval::<char>("''") {
         Err(_) => (),
-        _ => assert!(false),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_char_escapes() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<char>("'\\u{1F600}'"), Ok('😀'));
+    assert_eq!(engine.eval::<char>("'\\x41'"), Ok('A'));
+    assert_eq!(engine.eval::<char>("'\\''"), Ok('\''));
+    assert_eq!(engine.eval::<String>("\"\\x41\\u{e9}\\\"!\""), Ok("Aé\"!".to_string()));
+
+    // Surrogates, values past the last code point and multiple chars are rejected
+    assert_eq!(engine.eval::<char>("'\\u{D800}'"), Err(EvalAltResult::ErrorParsing(ParseError::BadInput)));
+    assert_eq!(engine.eval::<char>("'\\u{110000}'"), Err(EvalAltResult::ErrorParsing(ParseError::BadInput)));
+    assert_eq!(engine.eval::<char>("'\\u{}'"), Err(EvalAltResult::ErrorParsing(ParseError::BadInput)));
+    assert_eq!(engine.eval::<char>("'e\\u{301}'"), Err(EvalAltResult::ErrorParsing(ParseError::BadInput)));
+
+    assert_eq!(tokenize("'\\u{D800}'")[0].0, Token::LexErr(LexError::MalformedEscapeSequence));
+    assert_eq!(tokenize("'ab'")[0].0, Token::LexErr(LexError::MalformedChar));
+}
+
+#[test]
+fn test_string_unicode_escapes() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("\"smile: \\u{1F600}\""), Ok("smile: 😀".to_string()));
+    assert_eq!(engine.eval::<String>("\"\\u{41}\\u{10FFFF}\""), Ok("A\u{10FFFF}".to_string()));
+    assert_eq!(engine.eval::<i64>("len(\"\\u{1F600}\\u{1F600}\")"), Ok(2));
+
+    // Missing braces, bad or too many digits, and values which aren't chars
+    for script in &["\"\\u{1F600\"", "\"\\u{zz}\"", "\"\\u{1000000}\"", "\"\\u{DFFF}\"", "\"\\u{}\""] {
+        assert_eq!(tokenize(script)[0].0, Token::LexErr(LexError::MalformedEscapeSequence), "{}", script);
     }
 }
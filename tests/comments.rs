@@ -1,6 +1,6 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{tokenize, Engine, EvalAltResult, LexError, ParseError, Position, Span, Token};
 
 #[test]
 fn test_comments() {
@@ -10,3 +10,44 @@ fn test_comments() {
 
 	assert!(engine.eval::<i64>("let /* I am a multiline comment, yay! */ x = 5; x").is_ok());
 }
+
+#[test]
+fn test_comment_markers_in_strings() {
+	let mut engine = Engine::new();
+
+	assert_eq!(engine.eval::<String>("\"/* not a comment */\""), Ok("/* not a comment */".to_string()));
+	assert_eq!(engine.eval::<String>("\"// nor this\""), Ok("// nor this".to_string()));
+	assert_eq!(engine.eval::<String>("let s = \"*/\"; /* a comment */ s"), Ok("*/".to_string()));
+	assert_eq!(engine.eval::<char>("'/' /* a comment */"), Ok('/'));
+
+	// Quotes mean nothing inside a comment
+	assert_eq!(engine.eval::<i64>("/* \" */ 1 /* \" */"), Ok(1));
+}
+
+#[test]
+fn test_nested_block_comments() {
+	let mut engine = Engine::new();
+
+	assert_eq!(engine.eval::<i64>("/* outer /* inner */ still outer */ 1"), Ok(1));
+	assert_eq!(engine.eval::<i64>("/* a **/ 2"), Ok(2));
+	assert_eq!(engine.eval::<i64>("/*/ 3 */ 4"), Ok(4));
+	assert_eq!(engine.eval::<i64>("6 /* // */ / 2"), Ok(3));
+}
+
+#[test]
+fn test_unterminated_block_comment() {
+	let mut engine = Engine::new();
+
+	assert_eq!(engine.eval::<i64>("1 /* no end"),
+	           Err(EvalAltResult::ErrorParsing(ParseError::UnterminatedComment(Position { line: 1, column: 3 }))));
+	assert_eq!(engine.eval::<i64>("1;\n  /* /* */"),
+	           Err(EvalAltResult::ErrorParsing(ParseError::UnterminatedComment(Position { line: 2, column: 3 }))));
+	assert_eq!(engine.eval::<i64>("1 + /* no end"),
+	           Err(EvalAltResult::ErrorParsing(ParseError::UnterminatedComment(Position { line: 1, column: 5 }))));
+
+	let err = engine.eval::<i64>("1 /* no end").unwrap_err();
+	assert_eq!(err.to_string(), "Syntax error at line 1, col 3: Block comment not closed");
+
+	assert_eq!(tokenize("x = 1; /* /* */")[4],
+	           (Token::LexErr(LexError::UnterminatedComment), Span { start: 7, end: 15 }));
+}
@@ -0,0 +1,61 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, ParseError, Scope, ScopeExt};
+
+#[test]
+fn test_compile_once_eval_many() {
+    let mut engine = Engine::new();
+    let ast = engine.compile("let y = x * 2; y + 1").unwrap();
+
+    for x in 0..5i64 {
+        let mut scope = Scope::new();
+        scope.push_value("x", x);
+
+        assert_eq!(engine.eval_ast::<i64>(&mut scope, &ast), Ok(x * 2 + 1));
+    }
+}
+
+#[test]
+fn test_compile_keeps_scope() {
+    let mut engine = Engine::new();
+    let ast = engine.compile("x += 1; x").unwrap();
+    let mut scope = Scope::new();
+    scope.push_value("x", 0i64);
+
+    assert_eq!(engine.eval_ast::<i64>(&mut scope, &ast), Ok(1));
+    assert_eq!(engine.eval_ast::<i64>(&mut scope, &ast), Ok(2));
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "x"), Ok(2));
+}
+
+#[test]
+fn test_compile_functions() {
+    let mut engine = Engine::new();
+    let ast = engine.compile("fn add(a, b) { a + b } add(2, 3)").unwrap();
+
+    assert_eq!(engine.eval_ast::<i64>(&mut Scope::new(), &ast), Ok(5));
+    assert_eq!(engine.eval_ast::<i64>(&mut Scope::new(), &ast.clone()), Ok(5));
+    assert_eq!(engine.eval::<i64>("add(1, 1)"), Ok(2));
+
+    // Compiling alone doesn't define the script's functions
+    let engine = Engine::new();
+    engine.compile("fn sub(a, b) { a - b }").unwrap();
+    assert!(!engine.has_fn("sub", 2));
+}
+
+#[test]
+fn test_compile_errors() {
+    let mut engine = Engine::new();
+
+    match engine.compile("let = 5;") {
+        Err(EvalAltResult::ErrorParsing(ParseError::VarExpectsIdentifier)) => (),
+        other => panic!("expected a parse error, got {:?}", other.map(|_| ())),
+    }
+
+    let ast = engine.compile("nope").unwrap();
+    assert!(engine.eval_ast::<i64>(&mut Scope::new(), &ast).is_err());
+
+    let ast = engine.compile("\"hi\"").unwrap();
+    assert_eq!(engine.eval_ast::<i64>(&mut Scope::new(), &ast),
+               Err(EvalAltResult::ErrorMismatchOutputType("string".into())));
+    assert_eq!(engine.eval_ast::<()>(&mut Scope::new(), &ast), Ok(()));
+}
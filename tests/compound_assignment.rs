@@ -0,0 +1,73 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, Position, RegisterFn};
+
+#[derive(Clone)]
+struct Point {
+    x: i64,
+}
+
+impl Point {
+    fn get_x(&mut self) -> i64 {
+        self.x
+    }
+
+    fn set_x(&mut self, x: i64) {
+        self.x = x;
+    }
+
+    fn new() -> Point {
+        Point { x: 1 }
+    }
+}
+
+#[test]
+fn test_compound_assignment() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = 5; x += 4; x -= 3; x *= 2; x /= 4; x"), Ok(3));
+    assert_eq!(engine.eval::<i64>("let x = 17; x %= 5; x"), Ok(2));
+    assert_eq!(engine.eval::<f64>("let x = 1.5; x *= 3.0; x"), Ok(4.5));
+}
+
+#[test]
+fn test_compound_assignment_targets() {
+    let mut engine = Engine::new();
+
+    engine.register_type::<Point>();
+    engine.register_get_set("x", Point::get_x, Point::set_x);
+    engine.register_fn("new_point", Point::new);
+
+    assert_eq!(engine.eval::<i64>("let a = [1, 2, 3]; a[1] += 5; a[2] *= a[1]; a[2]"), Ok(21));
+    assert_eq!(engine.eval::<i64>("let p = new_point(); p.x += 4; p.x -= 2; p.x"), Ok(3));
+}
+
+#[test]
+fn test_compound_assignment_missing_target() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("y += 1"), Err(EvalAltResult::ErrorVariableNotFound("y".into(), Position { line: 1, column: 1 })));
+    assert_eq!(engine.eval::<i64>("y[0] -= 1"), Err(EvalAltResult::ErrorVariableNotFound("y".into(), Position { line: 1, column: 1 })));
+    assert_eq!(engine.eval::<i64>("y.x *= 2"), Err(EvalAltResult::ErrorVariableNotFound("y".into(), Position { line: 1, column: 1 })));
+}
+
+#[test]
+fn test_update_with_other_operators() {
+    let mut engine = Engine::new();
+
+    // Only arithmetic and bitwise operators have in-place versions, so a
+    // comparison assigned back to its own variable isn't mistaken for `<=`
+    assert_eq!(engine.eval::<bool>("let x = 5; x = x < 3; x"), Ok(false));
+    assert_eq!(engine.eval::<bool>("let x = 5; x = x > 3; x"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let x = 5; x = x == 5; x"), Ok(true));
+}
+
+#[test]
+fn test_update_evaluation_order() {
+    let mut engine = Engine::new();
+
+    // The variable is read before the right-hand side runs, as for any other call
+    assert_eq!(engine.eval::<String>("let s = \"a\"; s = s + (s = \"b\"); s"), Ok("ab".to_string()));
+    assert_eq!(engine.eval::<String>("let s = \"a\"; s += (s = \"b\"); s"), Ok("ab".to_string()));
+    assert_eq!(engine.eval::<i64>("let x = 1; x = x + (x = 10); x"), Ok(11));
+}
@@ -0,0 +1,40 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, Position, Scope};
+
+#[test]
+fn test_builtin_constants() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<f64>("PI"), Ok(::std::f64::consts::PI));
+    assert_eq!(engine.eval::<f64>("fn area(r) { PI * r * r } area(1.0)"),
+               Ok(::std::f64::consts::PI));
+}
+
+#[test]
+fn test_register_constant() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    engine.register_constant("ANSWER", 42i64);
+    engine.register_constant("GREETING", "hello".to_string());
+
+    assert_eq!(engine.eval::<i64>("ANSWER - 2"), Ok(40));
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "ANSWER"), Ok(42));
+    assert_eq!(engine.eval::<String>("GREETING.clone()"), Ok("hello".to_string()));
+    assert_eq!(engine.eval::<i64>("let ANSWER = 1; ANSWER"), Ok(1));
+}
+
+#[test]
+fn test_constant_is_read_only() {
+    let mut engine = Engine::new();
+
+    engine.register_constant("ANSWER", 42i64);
+
+    assert_eq!(engine.eval::<()>("ANSWER = 1"),
+               Err(EvalAltResult::ErrorAssignmentToConstant("ANSWER".to_string())));
+    assert_eq!(engine.eval::<()>("ANSWER += 1"),
+               Err(EvalAltResult::ErrorAssignmentToConstant("ANSWER".to_string())));
+    assert_eq!(engine.eval::<()>("missing = 1"),
+               Err(EvalAltResult::ErrorVariableNotFound("missing".to_string(), Position { line: 1, column: 1 })));
+}
@@ -0,0 +1,37 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, ParseError, Position, Scope};
+
+#[test]
+fn test_consume_collect_errors() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    let errors = engine.consume_collect_errors(&mut scope, "let width = 80; \
+                                                            let height = nope; \
+                                                            let depth = 2; \
+                                                            width = width + \"px\"; \
+                                                            let title = \"main\";");
+
+    assert_eq!(errors,
+               vec![(1, EvalAltResult::ErrorVariableNotFound("nope".to_string(), Position { line: 1, column: 30 })),
+                    (3, EvalAltResult::ErrorFunctionNotFound("+ (integer,string)".to_string(), Position { line: 1, column: 65 }))]);
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "width * depth"), Ok(160));
+    assert_eq!(engine.eval_with_scope::<String>(&mut scope, "title"), Ok("main".to_string()));
+
+    assert!(engine.consume_collect_errors(&mut Scope::new(), "let a = 1; a += 1;").is_empty());
+}
+
+#[test]
+fn test_consume_collect_errors_stops() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.consume_collect_errors(&mut Scope::new(), "let a = 1; a = ;"),
+               vec![(0, EvalAltResult::ErrorParsing(ParseError::BadInput))]);
+    assert!(engine.consume_collect_errors(&mut Scope::new(), "return; undefined_fn()").is_empty());
+
+    let mut engine = Engine::builder().max_operations(10).build();
+
+    assert_eq!(engine.consume_collect_errors(&mut Scope::new(), "loop { } x; y;"),
+               vec![(0, EvalAltResult::ErrorTooManyOperations)]);
+}
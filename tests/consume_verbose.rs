@@ -0,0 +1,28 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, ParseError, Position};
+
+#[test]
+fn test_consume_verbose() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.consume_verbose(""), Ok(0));
+    assert_eq!(engine.consume_verbose("let x = 1; x += 1; fn f() { 1 } f();"), Ok(3));
+
+    // A top-level return stops the script, but still ran
+    assert_eq!(engine.consume_verbose("let x = 1; return x; x += 1;"), Ok(2));
+}
+
+#[test]
+fn test_consume_verbose_error() {
+    let mut engine = Engine::new();
+
+    assert_eq!(
+        engine.consume_verbose("let x = 1; let y = 2; z = x + y; x += 1;"),
+        Err((2, EvalAltResult::ErrorVariableNotFound("z".to_string(), Position { line: 1, column: 23 })))
+    );
+    assert_eq!(
+        engine.consume_verbose("let x = 1 let y = 2;"),
+        Err((0, EvalAltResult::ErrorParsing(ParseError::MissingSemicolon(Position { line: 1, column: 11 }))))
+    );
+}
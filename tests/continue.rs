@@ -0,0 +1,46 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult};
+
+#[test]
+fn test_continue() {
+    let mut engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<i64>("let i = 0; let sum = 0; while i < 10 { i += 1; if i % 2 == 0 { continue; } sum += i; } sum"),
+        Ok(25)
+    );
+    assert_eq!(
+        engine.eval::<i64>("let i = 0; let n = 0; loop { i += 1; if i > 5 { break; } if i == 3 { continue; } n += 1; } n"),
+        Ok(4)
+    );
+    assert_eq!(engine.eval::<i64>("let sum = 0; for i in 0..5 { if i == 2 { continue; } sum += i; } sum"), Ok(8));
+    assert_eq!(engine.eval::<i64>("let sum = 0; for x in [1, 2, 3] { if x == 1 { continue } sum += x; } sum"), Ok(5));
+}
+
+#[test]
+fn test_continue_nested() {
+    let mut engine = Engine::new();
+
+    // `continue` only skips the rest of the innermost loop
+    assert_eq!(
+        engine.eval::<i64>("let n = 0; for i in 0..3 { for j in 0..3 { if j == 1 { continue; } n += 1; } n += 10; } n"),
+        Ok(36)
+    );
+    assert_eq!(
+        engine.eval::<i64>("let n = 0; for i in 0..4 { if i > 0 { if i < 3 { { continue; } } } n += 1; } n"),
+        Ok(2)
+    );
+}
+
+#[test]
+fn test_continue_outside_loop() {
+    let mut engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<i64>("fn f() { continue; } let n = 0; for i in 0..3 { f(); n += 1; } n"),
+        Err(EvalAltResult::ErrorRuntime("Cannot continue out of function f, only in a loop".into()))
+    );
+    assert_eq!(engine.eval::<i64>("let x = 1; continue;"),
+               Err(EvalAltResult::ErrorRuntime("Cannot continue outside of a loop".into())));
+}
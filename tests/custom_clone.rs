@@ -0,0 +1,72 @@
+extern crate rhai;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rhai::{Engine, RegisterFn};
+
+// `Clone` shares the cell, like a reference
+#[derive(Clone)]
+struct Handle(Rc<RefCell<i64>>);
+
+impl Handle {
+    fn new(x: i64) -> Handle {
+        Handle(Rc::new(RefCell::new(x)))
+    }
+
+    fn get(&mut self) -> i64 {
+        *self.0.borrow()
+    }
+
+    fn set(&mut self, x: i64) {
+        *self.0.borrow_mut() = x;
+    }
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+
+    engine.register_fn("handle", Handle::new);
+    engine.register_fn("get", Handle::get);
+    engine.register_fn("set", Handle::set);
+
+    engine
+}
+
+const SCRIPT: &str = "let a = handle(1); let b = a; b.set(5); a.get()";
+
+#[test]
+fn test_default_clone() {
+    let mut engine = engine();
+    engine.register_type::<Handle>();
+
+    assert_eq!(engine.eval::<i64>(SCRIPT), Ok(5));
+}
+
+#[test]
+fn test_custom_clone() {
+    let mut engine = engine();
+    engine.register_type_with_clone(|h: &Handle| Handle::new(*h.0.borrow()));
+
+    // Each variable now holds its own cell
+    assert_eq!(engine.eval::<i64>(SCRIPT), Ok(1));
+    assert_eq!(engine.eval::<i64>("let arr = [handle(1)]; let h = arr[0]; h.set(5); arr[0].get()"), Ok(1));
+    assert_eq!(engine.eval::<i64>("let a = fill(handle(2), 2); let b = a[0]; b.set(7); a[1].get() + a[0].get()"), Ok(4));
+    assert_eq!(engine.eval::<i64>("let a = handle(1); let b = clone(a); b.set(5); a.get()"), Ok(1));
+}
+
+#[test]
+fn test_custom_clone_everywhere() {
+    let mut engine = engine();
+    engine.register_type_with_clone(|h: &Handle| Handle::new(*h.0.borrow()));
+
+    // The loop variable is a copy of the element
+    assert_eq!(engine.eval::<i64>("let arr = [handle(1)]; for h in arr { h.set(5); } arr[0].get()"), Ok(1));
+
+    // So is a handle inside an array which is itself copied
+    assert_eq!(engine.eval::<i64>("let a = [handle(1)]; let b = a; b[0].set(5); a[0].get()"), Ok(1));
+    assert_eq!(engine.eval::<i64>("let a = [[handle(1)]]; let b = a[0]; b[0].set(5); let c = a[0]; c[0].get()"), Ok(1));
+
+    // And an argument given to a script function
+    assert_eq!(engine.eval::<i64>("fn f(h) { h.set(5); } let a = handle(1); f(a); a.get()"), Ok(1));
+}
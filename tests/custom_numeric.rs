@@ -0,0 +1,72 @@
+extern crate rhai;
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use rhai::{Engine, RegisterFn};
+
+// Two decimal places, stored as hundredths
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+struct Fixed(i64);
+
+impl Add for Fixed {
+    type Output = Fixed;
+    fn add(self, rhs: Fixed) -> Fixed { Fixed(self.0 + rhs.0) }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+    fn sub(self, rhs: Fixed) -> Fixed { Fixed(self.0 - rhs.0) }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+    fn mul(self, rhs: Fixed) -> Fixed { Fixed(self.0 * rhs.0 / 100) }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+    fn div(self, rhs: Fixed) -> Fixed { Fixed(self.0 * 100 / rhs.0) }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+    fn neg(self) -> Fixed { Fixed(-self.0) }
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+
+    engine.register_arithmetic::<Fixed>();
+    engine.register_comparison::<Fixed>();
+    engine.register_negation::<Fixed>();
+    engine.register_fn("fixed", Fixed);
+
+    engine
+}
+
+#[test]
+fn test_custom_arithmetic() {
+    let mut engine = engine();
+
+    assert_eq!(engine.eval::<Fixed>("fixed(150) + fixed(25)"), Ok(Fixed(175)));
+    assert_eq!(engine.eval::<Fixed>("fixed(150) - fixed(25)"), Ok(Fixed(125)));
+    assert_eq!(engine.eval::<Fixed>("fixed(150) * fixed(200)"), Ok(Fixed(300)));
+    assert_eq!(engine.eval::<Fixed>("fixed(150) / fixed(50)"), Ok(Fixed(300)));
+    assert_eq!(engine.eval::<Fixed>("let x = fixed(150); -x"), Ok(Fixed(-150)));
+    assert_eq!(engine.eval::<Fixed>("let x = fixed(100); x += fixed(5); x"), Ok(Fixed(105)));
+    assert_eq!(engine.eval::<Fixed>("sum([fixed(1), fixed(2), fixed(3)])"), Ok(Fixed(6)));
+
+    // The built-in overloads are untouched
+    assert_eq!(engine.eval::<i64>("1 + 2"), Ok(3));
+}
+
+#[test]
+fn test_custom_comparison() {
+    let mut engine = engine();
+
+    assert_eq!(engine.eval::<bool>("fixed(150) > fixed(25)"), Ok(true));
+    assert_eq!(engine.eval::<bool>("fixed(150) <= fixed(25)"), Ok(false));
+    assert_eq!(engine.eval::<bool>("fixed(25) == fixed(25)"), Ok(true));
+    assert_eq!(engine.eval::<bool>("fixed(25) != fixed(25)"), Ok(false));
+    assert_eq!(engine.eval::<Fixed>("max([fixed(1), fixed(3), fixed(2)])"), Ok(Fixed(3)));
+}
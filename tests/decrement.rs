@@ -6,15 +6,7 @@ use rhai::Engine;
 fn test_decrement() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("let x = 10; x -= 7; x") {
-        assert_eq!(result, 3);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("let x = 10; x -= 7; x"), Ok(3));
 
-    if let Ok(_) = engine.eval::<String>("let s = \"test\"; s -= \"ing\"; s") {
-        assert!(false);
-    } else {
-        assert!(true);
-    }
+    assert!(engine.eval::<String>("let s = \"test\"; s -= \"ing\"; s").is_err());
 }
@@ -0,0 +1,81 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, ParseError};
+use std::thread;
+
+// Run on a thread with a small stack, so running out of it fails the test.
+// Errors can't be sent back across threads, so compare them as text.
+fn eval_small_stack(script: String) -> Result<i64, String> {
+    thread::Builder::new()
+        .stack_size(2 * 1024 * 1024)
+        .spawn(move || Engine::new().eval::<i64>(&script).map_err(|e| format!("{:?}", e)))
+        .unwrap()
+        .join()
+        .unwrap()
+}
+
+fn text(err: EvalAltResult) -> Result<i64, String> {
+    Err(format!("{:?}", err))
+}
+
+#[test]
+fn test_long_expression() {
+    // Operators which bind left to right don't nest, however many there are
+    let script = vec!["1"; 10_000].join(" + ");
+    assert_eq!(eval_small_stack(script), Ok(10_000));
+
+    let script = vec!["2 * 3"; 10_000].join(" - ");
+    assert_eq!(eval_small_stack(script), Ok(6 - 6 * 9_999));
+
+    let script = format!("let x = 0; {}; x", vec!["x += 1"; 10_000].join("; "));
+    assert_eq!(eval_small_stack(script), Ok(10_000));
+
+    // Assignments bind right to left, so each one does
+    let script = format!("let x = 0; {}1", "x = ".repeat(10_000));
+    assert_eq!(eval_small_stack(script), text(EvalAltResult::ErrorParsing(ParseError::ExprTooDeep)));
+}
+
+#[test]
+fn test_depth_after_error() {
+    let mut engine = Engine::new();
+
+    // A parse which fails deep inside doesn't leave its depth behind for the next one
+    let script = format!("{}1 +", "(".repeat(100));
+    assert!(engine.eval::<i64>(&script).is_err());
+
+    let script = format!("{}1{}", "(".repeat(50), ")".repeat(50));
+    assert_eq!(engine.eval::<i64>(&script), Ok(1));
+}
+
+#[test]
+fn test_nested_parens_and_blocks() {
+    let script = format!("{}1{}", "(".repeat(10_000), ")".repeat(10_000));
+    assert_eq!(eval_small_stack(script), text(EvalAltResult::ErrorParsing(ParseError::ExprTooDeep)));
+
+    let script = format!("{}1", "-".repeat(10_000));
+    assert_eq!(eval_small_stack(script), text(EvalAltResult::ErrorParsing(ParseError::ExprTooDeep)));
+
+    let script = format!("{}1{}", "{ ".repeat(10_000), " }".repeat(10_000));
+    assert_eq!(eval_small_stack(script), text(EvalAltResult::ErrorParsing(ParseError::ExprTooDeep)));
+
+    let script = format!("{}1{}", "(".repeat(50), ")".repeat(50));
+    assert_eq!(eval_small_stack(script), Ok(1));
+}
+
+#[test]
+fn test_runaway_recursion() {
+    let script = "fn f(x) { f(x + 1) } f(1)".to_string();
+    assert_eq!(eval_small_stack(script), text(EvalAltResult::ErrorStackOverflow));
+
+    let script = "fn f(x) { if x == 0 { return 0; } 1 + f(x - 1) } f(50)".to_string();
+    assert_eq!(eval_small_stack(script), Ok(50));
+}
+
+#[test]
+fn test_max_call_depth() {
+    let mut engine = Engine::builder().max_call_depth(5).build();
+
+    assert_eq!(engine.eval::<i64>("fn f(x) { if x == 0 { return 0; } 1 + f(x - 1) } f(4)"), Ok(4));
+    assert_eq!(engine.eval::<i64>("fn f(x) { if x == 0 { return 0; } 1 + f(x - 1) } f(5)"),
+               Err(EvalAltResult::ErrorStackOverflow));
+}
@@ -0,0 +1,87 @@
+extern crate rhai;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rhai::{Engine, RegisterFn};
+
+#[derive(Clone)]
+struct Vec2 {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Clone)]
+struct Opaque;
+
+fn new_engine() -> Engine {
+    let mut engine = Engine::new();
+
+    engine.register_type_name::<Vec2>("Vec2");
+    engine.register_type_name::<Opaque>("Opaque");
+    engine.register_fn("vec2", |x: f64, y: f64| Vec2 { x, y });
+    engine.register_fn("opaque", || Opaque);
+    engine.register_display(|v: &Vec2| format!("({}, {})", v.x, v.y));
+
+    engine
+}
+
+#[test]
+fn test_display_to_string() {
+    let mut engine = new_engine();
+
+    assert_eq!(engine.eval::<String>("to_string(vec2(1.5, 2.0))"), Ok("(1.5, 2)".to_string()));
+    assert_eq!(engine.eval::<String>("let v = vec2(1.0, 0.0); v.to_string()"), Ok("(1, 0)".to_string()));
+    assert_eq!(engine.eval::<String>("to_string(opaque())"), Ok("<Opaque>".to_string()));
+    assert_eq!(engine.eval::<String>("to_string(42)"), Ok("42".to_string()));
+    assert_eq!(engine.eval::<String>("to_string([1, \"a\", 'b', vec2(0.0, 1.0)])"),
+               Ok("[1, \"a\", 'b', (0, 1)]".to_string()));
+    assert_eq!(engine.eval::<String>("debug(\"hi\")"), Ok("\"hi\"".to_string()));
+}
+
+#[test]
+fn test_display_concat() {
+    let mut engine = new_engine();
+
+    assert_eq!(engine.eval::<String>("\"at \" + vec2(1.0, 2.0)"), Ok("at (1, 2)".to_string()));
+    assert_eq!(engine.eval::<String>("vec2(1.0, 2.0) + \"!\""), Ok("(1, 2)!".to_string()));
+}
+
+#[test]
+fn test_display_print() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let sink = log.clone();
+
+    let mut engine = Engine::builder()
+        .on_print(move |s| sink.borrow_mut().push(s.to_string()))
+        .build();
+
+    engine.register_type_name::<Vec2>("Vec2");
+    engine.register_fn("vec2", |x: f64, y: f64| Vec2 { x, y });
+    engine.register_display(|v: &Vec2| format!("({}, {})", v.x, v.y));
+
+    engine.consume("print(vec2(3.0, 4.0)); print([1, 2]); print(\"plain\")").unwrap();
+
+    assert_eq!(*log.borrow(), vec!["(3, 4)", "[1, 2]", "plain"]);
+}
+
+#[test]
+fn test_display_script_fn_same_name() {
+    let mut engine = Engine::builder().sandboxed().build();
+
+    // A script's own `to_string` is used for every type while it's defined,
+    // and the builtin is used again once the script function is forgotten
+    assert_eq!(engine.eval::<String>("fn to_string(x) { \"mine\" } to_string(1) + to_string(true)"),
+               Ok("minemine".to_string()));
+    assert_eq!(engine.eval::<String>("to_string(1)"), Ok("1".to_string()));
+
+    // Without sandboxing, script functions outlive the script, and so does
+    // the one standing in for the builtin
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("fn debug(x) { \"mine\" } debug(1)"), Ok("mine".to_string()));
+    assert_eq!(engine.eval::<String>("debug(1)"), Ok("mine".to_string()));
+    assert!(engine.has_fn("to_string", 1));
+    assert_eq!(engine.explain_call("to_string", &["boolean"]),
+               Some("to_string, registered for arguments of any type".to_string()));
+}
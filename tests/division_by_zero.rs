@@ -0,0 +1,54 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, RegisterFn};
+
+#[test]
+fn test_division_by_zero() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("10 / 3"), Ok(3));
+    assert_eq!(engine.eval::<i64>("10 % 3"), Ok(1));
+    assert_eq!(engine.eval::<i64>("10 / 0"), Err(EvalAltResult::ErrorArithmetic("Division by zero: 10 / 0".into())));
+    assert_eq!(engine.eval::<i64>("10 % 0"), Err(EvalAltResult::ErrorArithmetic("Division by zero: 10 % 0".into())));
+    assert_eq!(engine.eval::<i64>("let x = 7; let y = 0; x / y"),
+               Err(EvalAltResult::ErrorArithmetic("Division by zero: 7 / 0".into())));
+    assert_eq!(engine.eval::<i64>("let x = 7; x /= 0; x"),
+               Err(EvalAltResult::ErrorArithmetic("Division by zero: 7 / 0".into())));
+    assert_eq!(engine.eval::<i64>("let x = 7; x %= 0; x"),
+               Err(EvalAltResult::ErrorArithmetic("Division by zero: 7 % 0".into())));
+}
+
+#[test]
+fn test_division_overflow() {
+    let mut engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<i64>("let x = -9223372036854775807 - 1; x / -1"),
+        Err(EvalAltResult::ErrorArithmetic("Division overflow: -9223372036854775808 / -1".into()))
+    );
+    assert!(engine.eval::<i64>("let x = -9223372036854775807 - 1; x % -1").is_err());
+}
+
+#[test]
+fn test_division_by_zero_host_types() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("int32", |x: i64| x as i32);
+    engine.register_fn("uint32", |x: i64| x as u32);
+    engine.register_fn("uint64", |x: i64| x as u64);
+
+    assert_eq!(engine.eval::<i32>("int32(-7) / int32(2)"), Ok(-3));
+    assert_eq!(engine.eval::<u64>("uint64(7) % uint64(4)"), Ok(3));
+    assert!(engine.eval::<i32>("int32(1) / int32(0)").is_err());
+    assert!(engine.eval::<u32>("uint32(1) % uint32(0)").is_err());
+    assert!(engine.eval::<u64>("uint64(1) / uint64(0)").is_err());
+}
+
+#[test]
+fn test_float_division_by_zero() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<f64>("1.0 / 0.0"), Ok(f64::INFINITY));
+    assert_eq!(engine.eval::<f64>("-1.0 / 0.0"), Ok(f64::NEG_INFINITY));
+    assert!(engine.eval::<f64>("0.0 / 0.0").unwrap().is_nan());
+}
@@ -0,0 +1,75 @@
+extern crate rhai;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rhai::{Engine, EngineBuilder, EvalAltResult};
+
+#[test]
+fn test_builder_default() {
+    let mut engine = EngineBuilder::new().build();
+
+    assert_eq!(engine.eval::<i64>("40 + 2"), Ok(42));
+}
+
+#[test]
+fn test_builder_without_default_lib() {
+    let mut engine = Engine::builder().without_default_lib().build();
+
+    match engine.eval::<i64>("40 + 2") {
+        Err(EvalAltResult::ErrorFunctionNotFound(_, _)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_builder_max_operations() {
+    let mut engine = Engine::builder().max_operations(100).build();
+
+    assert_eq!(engine.eval::<()>("loop { }"), Err(EvalAltResult::ErrorTooManyOperations));
+    assert_eq!(engine.eval::<i64>("let x = 0; while x < 10 { x += 1; } x"), Ok(10));
+}
+
+#[test]
+fn test_builder_max_operations_in_one_statement() {
+    let mut engine = Engine::builder().max_operations(1000).build();
+
+    // Each of these is a single statement, but does far more than 1000 operations
+    let calls = "fn a() { 1 } fn b() { a() + a() + a() + a() } fn c() { b() + b() + b() + b() } \
+                 fn d() { c() + c() + c() + c() } fn e() { d() + d() + d() + d() }";
+    assert_eq!(engine.eval::<i64>(&format!("{} d()", calls)), Ok(64));
+    assert_eq!(engine.eval::<i64>(&format!("{} e()", calls)), Err(EvalAltResult::ErrorTooManyOperations));
+    assert_eq!(engine.eval::<i64>("len(fill(0, 5000))"), Err(EvalAltResult::ErrorTooManyOperations));
+    assert_eq!(engine.eval::<i64>("len(\"ab\" * 5000)"), Err(EvalAltResult::ErrorTooManyOperations));
+
+    assert_eq!(engine.eval::<i64>("len(fill(0, 500))"), Ok(500));
+}
+
+#[test]
+fn test_builder_on_print() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let sink = log.clone();
+
+    let mut engine = Engine::builder()
+        .on_print(move |s| sink.borrow_mut().push(s.to_string()))
+        .build();
+
+    assert!(engine.consume("print(42); print(\"hello\"); print(true)").is_ok());
+    assert_eq!(*log.borrow(), vec!["42", "hello", "true"]);
+}
+
+#[test]
+fn test_builder_sandboxed() {
+    let mut engine = Engine::builder().sandboxed().build();
+
+    assert_eq!(engine.eval::<i64>("fn f() { 1 } f()"), Ok(1));
+    match engine.eval::<i64>("f()") {
+        Err(EvalAltResult::ErrorFunctionNotFound(_, _)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("fn f() { 1 } f()"), Ok(1));
+    assert_eq!(engine.eval::<i64>("f()"), Ok(1));
+}
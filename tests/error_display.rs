@@ -0,0 +1,87 @@
+extern crate rhai;
+
+use rhai::{tokenize, Engine, EvalAltResult, Position, Token};
+
+#[test]
+fn test_position_from_offset() {
+    let src = "let x = 1;\nlet y = nope;";
+    let offset = tokenize(src)
+        .into_iter()
+        .find(|(token, _)| *token == Token::Identifier("nope".into()))
+        .unwrap()
+        .1
+        .start;
+
+    assert_eq!(Position::from_offset(src, offset), Position { line: 2, column: 9 });
+    assert_eq!(Position::from_offset(src, 0), Position { line: 1, column: 1 });
+
+    // Offsets inside a char or past the end don't panic
+    let src = "\"é\"";
+    assert_eq!(Position::from_offset(src, 2), Position { line: 1, column: 2 });
+    assert_eq!(Position::from_offset(src, 100), Position { line: 1, column: 4 });
+}
+
+#[test]
+fn test_position_snippet() {
+    let src = "let x = 1;\nlet y = nope;";
+    let pos = Position { line: 2, column: 9 };
+
+    assert_eq!(pos.to_string(), "line 2, col 9");
+    assert_eq!(pos.snippet(src), Some("2 | let y = nope;\n  |         ^".to_string()));
+
+    // There's nothing to show for lines the script doesn't have
+    assert_eq!(Position::none().snippet(src), None);
+    assert_eq!(Position { line: 3, column: 1 }.snippet(src), None);
+}
+
+#[test]
+fn test_display_with_source() {
+    let mut engine = Engine::new();
+    let src = "let x = 1;\nnope(x)";
+
+    let err = engine.eval::<i64>(src).unwrap_err();
+    assert_eq!(err.display_with_source(src),
+               "Function not found at line 2, col 1: nope (integer)\n2 | nope(x)\n  | ^");
+
+    // Errors without a position are shown as they are
+    let err = engine.eval::<i64>("let x = 1;\n1 ~ -1").unwrap_err();
+    assert_eq!(err.display_with_source(src), err.to_string());
+}
+
+#[test]
+fn test_error_positions() {
+    let mut engine = Engine::new();
+
+    let err = engine.eval::<i64>("let x = 1;\nlet y = x +\n    nope;").unwrap_err();
+    assert_eq!(err, EvalAltResult::ErrorVariableNotFound("nope".into(), Position { line: 3, column: 5 }));
+    assert_eq!(err.position(), Some(Position { line: 3, column: 5 }));
+    assert_eq!(err.to_string(), "Variable not found at line 3, col 5: nope");
+
+    // An operator with no function for its operand types points at the operator
+    let err = engine.eval::<i64>("let s = \"a\";\n1 -  s").unwrap_err();
+    assert_eq!(err.position(), Some(Position { line: 2, column: 3 }));
+
+    // Unknown methods and properties point at their name
+    let err = engine.eval::<i64>("let s = \"a\";\ns.nope()").unwrap_err();
+    assert_eq!(err.position(), Some(Position { line: 2, column: 3 }));
+    let err = engine.eval::<i64>("let s = \"a\";\ns.size").unwrap_err();
+    assert_eq!(err.position(), Some(Position { line: 2, column: 3 }));
+
+    // Inside a function, the error points into its body rather than at the call
+    let err = engine.eval::<i64>("fn f(x) {\n  x + missing\n}\nf(1)").unwrap_err();
+    assert_eq!(err, EvalAltResult::ErrorVariableNotFound("missing".into(), Position { line: 2, column: 7 }));
+
+    // Chars before the error count once each, even if they take several bytes
+    let err = engine.eval::<i64>("\"é\" + nope").unwrap_err();
+    assert_eq!(err.position(), Some(Position { line: 1, column: 7 }));
+}
+
+#[test]
+fn test_error_without_position() {
+    let engine = Engine::new();
+
+    let mut x = 1i64;
+    let err = engine.call_fn::<_, _, i64>("nope", (&mut x,)).unwrap_err();
+    assert_eq!(err.position(), None);
+    assert_eq!(err.to_string(), "Function not found: nope (integer)");
+}
@@ -0,0 +1,35 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, Position, Scope};
+
+#[test]
+fn test_eval_dynamic() {
+    let mut engine = Engine::new();
+
+    let value = engine.eval_dynamic("40 + 2").unwrap();
+    assert_eq!(value.downcast_ref::<i64>(), Some(&42));
+
+    let value = engine.eval_dynamic("\"a\" + \"b\"").unwrap();
+    assert_eq!(value.downcast_ref::<String>(), Some(&"ab".to_string()));
+
+    let value = engine.eval_dynamic("let x = 1;").unwrap();
+    assert!(value.downcast_ref::<()>().is_some());
+}
+
+#[test]
+fn test_eval_dynamic_with_scope() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    engine.eval_dynamic_with_scope(&mut scope, "let x = 5; fn double(n) { n * 2 }").unwrap();
+
+    let value = engine.eval_dynamic_with_scope(&mut scope, "x * 3").unwrap();
+    assert_eq!(value.downcast_ref::<i64>(), Some(&15));
+
+    let value = engine.eval_dynamic_with_scope(&mut scope, "x += 1; double(x) > 10").unwrap();
+    assert_eq!(value.downcast_ref::<bool>(), Some(&true));
+
+    assert_eq!(engine.eval_dynamic_with_scope(&mut scope, "y").map(|_| ()),
+               Err(EvalAltResult::ErrorVariableNotFound("y".to_string(), Position { line: 1, column: 1 })));
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "x"), Ok(6));
+}
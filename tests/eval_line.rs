@@ -0,0 +1,20 @@
+extern crate rhai;
+
+use rhai::{Engine, Scope};
+
+#[test]
+fn test_eval_line() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    assert!(engine.eval_line(&mut scope, "let x = 5;").unwrap().is_none());
+    assert!(engine.eval_line(&mut scope, "x = x + 1").unwrap().is_none());
+
+    let value = engine.eval_line(&mut scope, "x + 1").unwrap().unwrap();
+    assert_eq!(value.downcast_ref::<i64>(), Some(&7));
+
+    let value = engine.eval_line(&mut scope, "let y = 2; y * x").unwrap().unwrap();
+    assert_eq!(value.downcast_ref::<i64>(), Some(&12));
+
+    assert!(engine.eval_line(&mut scope, "while false { }").unwrap().is_none());
+}
@@ -0,0 +1,57 @@
+extern crate rhai;
+
+use rhai::{Any, Engine, RegisterFn};
+
+#[test]
+fn test_eval_array_output() {
+    let mut engine = Engine::new();
+
+    let arr = engine.eval::<Vec<Box<dyn Any>>>("[1, \"two\", [3]]").unwrap();
+
+    assert_eq!(arr.len(), 3);
+    assert_eq!(arr[0].downcast_ref::<i64>(), Some(&1));
+    assert_eq!(arr[1].downcast_ref::<String>(), Some(&"two".to_string()));
+
+    let inner = arr[2].downcast_ref::<Vec<Box<dyn Any>>>().unwrap();
+    assert_eq!(inner[0].downcast_ref::<i64>(), Some(&3));
+}
+
+#[test]
+fn test_eval_array_from_variable() {
+    let mut engine = Engine::new();
+
+    let arr = engine.eval::<Vec<Box<dyn Any>>>("let x = [1, 2]; x[1] = 5; x").unwrap();
+
+    assert_eq!(arr[1].downcast_ref::<i64>(), Some(&5));
+    assert_eq!(engine.eval::<String>("let s = \"abc\"; s"), Ok("abc".to_string()));
+}
+
+#[test]
+fn test_eval_dynamic_host_value() {
+    let mut engine = Engine::new();
+
+    fn make_list() -> Vec<Box<dyn Any>> {
+        vec![Box::new(1i64), Box::new(2i64)]
+    }
+
+    fn make_dynamic() -> Box<dyn Any> {
+        Box::new(make_list())
+    }
+
+    engine.register_fn("make_list", make_list);
+    engine.register_fn("make_dynamic", make_dynamic);
+
+    assert_eq!(engine.eval::<Vec<Box<dyn Any>>>("make_list()").unwrap().len(), 2);
+    assert_eq!(engine.eval::<Vec<Box<dyn Any>>>("make_dynamic()").unwrap().len(), 2);
+    assert_eq!(engine.eval::<i64>("let x = make_dynamic(); x[1]"), Ok(2));
+}
+
+#[test]
+fn test_eval_unit_discards_result() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<()>("5"), Ok(()));
+    assert_eq!(engine.eval::<()>("let x = [1, 2]; x"), Ok(()));
+    assert!(engine.eval::<()>("undefined_fn()").is_err());
+    assert!(engine.eval::<i64>("\"five\"").is_err());
+}
@@ -0,0 +1,47 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, Position, Scope};
+
+#[test]
+fn test_eval_stepwise() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+    let mut values = Vec::new();
+
+    let result = engine.eval_stepwise_with_scope(&mut scope, "let x = 2; x * 3; fn f() { 1 } x + f()", |r| {
+        values.push(r.as_ref().ok().and_then(|v| v.downcast_ref::<i64>().cloned()));
+        true
+    });
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(values, vec![None, Some(6), Some(3)]);
+}
+
+#[test]
+fn test_eval_stepwise_stop_early() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+    let mut count = 0;
+
+    engine.eval_stepwise_with_scope(&mut scope, "let x = 1; x = 2; x = 3", |_| {
+        count += 1;
+        count < 2
+    }).unwrap();
+
+    assert_eq!(count, 2);
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "x"), Ok(2));
+}
+
+#[test]
+fn test_eval_stepwise_error() {
+    let mut engine = Engine::new();
+    let mut errors = Vec::new();
+
+    let result = engine.eval_stepwise_with_scope(&mut Scope::new(), "1; nope; 3", |r| {
+        errors.push(r.is_err());
+        true
+    });
+
+    assert_eq!(result, Err(EvalAltResult::ErrorVariableNotFound("nope".into(), Position { line: 1, column: 4 })));
+    assert_eq!(errors, vec![false, true]);
+}
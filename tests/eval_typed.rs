@@ -0,0 +1,24 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, Position};
+
+#[test]
+fn test_eval_typed() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval_bool("1 < 2"), Ok(true));
+    assert_eq!(engine.eval_int("40 + 2"), Ok(42));
+    assert_eq!(engine.eval_float("1.5 * 2.0"), Ok(3.0));
+    assert_eq!(engine.eval_string("\"a\" + \"b\""), Ok("ab".to_string()));
+}
+
+#[test]
+fn test_eval_typed_mismatch() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval_int("\"42\""),
+               Err(EvalAltResult::ErrorMismatchOutputType("expected integer, got string".into())));
+    assert_eq!(engine.eval_bool("1"),
+               Err(EvalAltResult::ErrorMismatchOutputType("expected boolean, got integer".into())));
+    assert_eq!(engine.eval_float("nope"), Err(EvalAltResult::ErrorVariableNotFound("nope".into(), Position { line: 1, column: 1 })));
+}
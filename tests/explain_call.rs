@@ -0,0 +1,61 @@
+extern crate rhai;
+
+use rhai::{Engine, RegisterFn};
+
+#[derive(Clone)]
+struct Point;
+
+#[test]
+fn test_explain_call() {
+    let mut engine = Engine::new();
+
+    assert_eq!(
+        engine.explain_call("|", &["boolean", "boolean"]),
+        Some("| (boolean, boolean), registered for these argument types".to_string())
+    );
+    assert_eq!(
+        engine.explain_call("to_string", &["float"]),
+        Some("to_string, registered for arguments of any type".to_string())
+    );
+    assert_eq!(engine.explain_call("|", &["boolean", "integer"]), None);
+    assert_eq!(engine.explain_call("nope", &[]), None);
+
+    // Custom types need a name to be found
+    engine.register_fn("norm", |_: Point| 1.0);
+    assert_eq!(engine.explain_call("norm", &["Point"]), None);
+
+    engine.register_type_name::<Point>("Point");
+    assert_eq!(
+        engine.explain_call("norm", &["Point"]),
+        Some("norm (Point), registered for these argument types".to_string())
+    );
+}
+
+#[test]
+fn test_explain_call_fallbacks() {
+    let mut engine = Engine::new();
+
+    engine.consume("fn double(x) { x * 2 }").unwrap();
+
+    // A script function is only used if nothing matches the exact types
+    assert_eq!(
+        engine.explain_call("double", &["integer"]),
+        Some("double(x), defined by a script".to_string())
+    );
+
+    engine.register_fn("double", |x: i64| x + x);
+    assert_eq!(
+        engine.explain_call("double", &["integer"]),
+        Some("double (integer), registered for these argument types".to_string())
+    );
+    assert_eq!(
+        engine.explain_call("double", &["float"]),
+        Some("double(x), defined by a script".to_string())
+    );
+
+    engine.on_unknown_fn(|_, _| None);
+    assert_eq!(
+        engine.explain_call("nope", &["integer"]),
+        Some("nope, left to the on_unknown_fn resolver".to_string())
+    );
+}
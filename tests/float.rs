@@ -1,29 +1,17 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{Engine, EvalAltResult};
 use rhai::RegisterFn;
 
 #[test]
 fn test_float() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<bool>("let x = 0.0; let y = 1.0; x < y") {
-        assert!(result);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<bool>("let x = 0.0; let y = 1.0; x < y"), Ok(true));
 
-    if let Ok(result) = engine.eval::<bool>("let x = 0.0; let y = 1.0; x > y") {
-        assert!(!result);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<bool>("let x = 0.0; let y = 1.0; x > y"), Ok(false));
 
-    if let Ok(result) = engine.eval::<f64>("let x = 9.9999; x") {
-        assert_eq!(result, 9.9999);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<f64>("let x = 9.9999; x"), Ok(9.9999));
 }
 
 #[test]
@@ -59,15 +47,62 @@ fn struct_with_float() {
     engine.register_fn("update", TestStruct::update);
     engine.register_fn("new_ts", TestStruct::new);
 
-    if let Ok(result) = engine.eval::<f64>("let ts = new_ts(); ts.update(); ts.x") {
-        assert_eq!(result, 6.789);
-    } else {
-        assert!(false);
+    assert_eq!(engine.eval::<f64>("let ts = new_ts(); ts.update(); ts.x"), Ok(6.789));
+
+    assert_eq!(engine.eval::<f64>("let ts = new_ts(); ts.x = 10.1001; ts.x"), Ok(10.1001));
+}
+
+#[test]
+fn test_float_mixed_precision() {
+    #[derive(Clone)]
+    struct Particle {
+        speed: f32,
     }
 
-    if let Ok(result) = engine.eval::<f64>("let ts = new_ts(); ts.x = 10.1001; ts.x") {
-        assert_eq!(result, 10.1001);
-    } else {
-        assert!(false);
+    impl Particle {
+        fn get_speed(&mut self) -> f32 {
+            self.speed
+        }
+
+        fn new() -> Particle {
+            Particle { speed: 2.5 }
+        }
     }
+
+    let mut engine = Engine::new();
+
+    engine.register_type::<Particle>();
+    engine.register_get("speed", Particle::get_speed);
+    engine.register_fn("new_particle", Particle::new);
+
+    assert_eq!(engine.eval::<bool>("let p = new_particle(); p.speed > 2.0"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let p = new_particle(); 2.5 == p.speed"), Ok(true));
+    assert_eq!(engine.eval::<f64>("let p = new_particle(); p.speed * 2.0"), Ok(5.0));
+    assert_eq!(engine.eval::<f64>("let p = new_particle(); 1.0 - p.speed"), Ok(-1.5));
+    assert_eq!(engine.eval::<bool>("let p = new_particle(); let q = new_particle(); p.speed <= q.speed"),
+               Ok(true));
+}
+
+#[test]
+fn test_float_to_string() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("to_string(2.0)"), Ok("2.0".to_string()));
+    assert_eq!(engine.eval::<String>("to_string(0.1 + 0.2)"), Ok("0.30000000000000004".to_string()));
+    assert_eq!(engine.eval::<String>("to_string(-1.25)"), Ok("-1.25".to_string()));
+    assert_eq!(engine.eval::<String>("to_string(1000000.0 * 1000000.0 * 1000000000.0)"), Ok("1e21".to_string()));
+    assert_eq!(engine.eval::<String>("to_string(0.000001)"), Ok("1e-6".to_string()));
+    assert_eq!(engine.eval::<String>("to_string([1.0, 2])"), Ok("[1.0, 2]".to_string()));
+}
+
+#[test]
+fn test_format_float() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("format_float(19.989, 2)"), Ok("19.99".to_string()));
+    assert_eq!(engine.eval::<String>("format_float(5.0, 2)"), Ok("5.00".to_string()));
+    assert_eq!(engine.eval::<String>("format_float(2.5, 0)"), Ok("2".to_string()));
+    assert_eq!(engine.eval::<String>("format_float(-0.125, 1)"), Ok("-0.1".to_string()));
+    assert_eq!(engine.eval::<String>("format_float(1.0, -1)"),
+               Err(EvalAltResult::ErrorRuntime("Cannot format a float with -1 decimals".to_string())));
 }
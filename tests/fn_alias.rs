@@ -0,0 +1,15 @@
+extern crate rhai;
+
+use rhai::Engine;
+
+#[test]
+fn test_fn_alias() {
+    let mut engine = Engine::new();
+
+    engine.register_fn_alias("+", "add");
+
+    assert_eq!(engine.eval::<i64>("add(40, 2)"), Ok(42));
+    assert_eq!(engine.eval::<f64>("add(1.5, 2.0)"), Ok(3.5));
+    assert_eq!(engine.eval::<String>("add(\"a\", \"b\")"), Ok("ab".to_string()));
+    assert_eq!(engine.eval::<i64>("40 + 2"), Ok(42));
+}
@@ -0,0 +1,35 @@
+extern crate rhai;
+
+use rhai::{Engine, RegisterFnMut};
+
+#[test]
+fn test_fn_mut_counter() {
+    let mut engine = Engine::new();
+    let mut count: i64 = 0;
+
+    engine.register_fn_mut("next", move || {
+        count += 1;
+        count
+    });
+
+    assert_eq!(engine.eval::<i64>("next(); next(); next()"), Ok(3));
+    assert_eq!(engine.eval::<i64>("next()"), Ok(4));
+}
+
+#[test]
+fn test_fn_mut_seeded_rng() {
+    let mut engine = Engine::new();
+    let mut state: i64 = 42;
+
+    engine.register_fn_mut("rand", move |max: i64| {
+        state = (state * 1103515245 + 12345) % 2147483648;
+        state % max
+    });
+
+    let first = engine.eval::<i64>("rand(100)").unwrap();
+    let second = engine.eval::<i64>("rand(100)").unwrap();
+
+    assert!((0..100).contains(&first));
+    assert!((0..100).contains(&second));
+    assert!(first != second);
+}
@@ -0,0 +1,76 @@
+extern crate rhai;
+
+use std::any::TypeId;
+
+use rhai::{Engine, EvalAltResult, Scope, ScopeExt};
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+
+    // Names of all variables in scope, innermost last
+    engine.register_fn_with_scope("dump_vars", Some(vec![]), |scope: &mut Scope, _| {
+        let names: Vec<&str> = scope.iter().map(|(name, _)| name.as_str()).collect();
+        Ok(Box::new(names.join(",")))
+    });
+
+    // Sets an existing variable, or creates it in the innermost block
+    engine.register_fn_with_scope("set_var", None, |scope: &mut Scope, mut args| {
+        if args.len() != 2 {
+            return Err(EvalAltResult::ErrorFunctionArgMismatch);
+        }
+
+        let value = args.pop().unwrap().box_clone();
+        let name = args[0]
+            .downcast_ref::<String>()
+            .ok_or(EvalAltResult::ErrorFunctionArgMismatch)?
+            .clone();
+
+        match scope.iter_mut().rev().find(|(n, _)| *n == name) {
+            Some(var) => var.1 = value,
+            None => scope.push((name, value)),
+        }
+
+        Ok(Box::new(()))
+    });
+
+    engine
+}
+
+#[test]
+fn test_fn_with_scope() {
+    let mut engine = engine();
+
+    assert_eq!(engine.eval::<String>("let a = 1; { let b = 2; dump_vars() }"), Ok("a,b".to_string()));
+    assert_eq!(engine.eval::<i64>("let a = 1; set_var(\"a\", 5); a"), Ok(5));
+    assert_eq!(engine.eval::<String>("set_var(\"greeting\", \"hi\"); greeting"), Ok("hi".to_string()));
+
+    // A script function only sees its own variables
+    assert_eq!(engine.eval::<String>("fn f(x) { dump_vars() } let a = 1; f(2)"), Ok("x".to_string()));
+}
+
+#[test]
+fn test_fn_with_scope_from_host() {
+    let mut engine = engine();
+    let mut scope = Scope::new();
+
+    scope.push_value("a", 1i64);
+    assert_eq!(engine.eval_with_scope::<String>(&mut scope, "dump_vars()"), Ok("a".to_string()));
+
+    engine.eval_with_scope::<()>(&mut scope, "set_var(\"b\", 2)").unwrap();
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "b"), Ok(2));
+
+    assert_eq!(engine.call_fn::<_, _, String>("dump_vars", ()), Ok("".to_string()));
+}
+
+#[test]
+fn test_fn_with_scope_arg_types() {
+    let mut engine = Engine::new();
+
+    engine.register_fn_with_scope("count_vars", Some(vec![TypeId::of::<i64>()]), |scope: &mut Scope, args| {
+        let min = *args[0].downcast_ref::<i64>().unwrap();
+        Ok(Box::new(scope.len() as i64 >= min))
+    });
+
+    assert_eq!(engine.eval::<bool>("let a = 1; count_vars(1)"), Ok(true));
+    assert!(engine.eval::<bool>("count_vars(\"a\")").is_err());
+}
@@ -0,0 +1,43 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult};
+
+#[test]
+fn test_for_array() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let sum = 0; for x in [1, 2, 3, 4] { sum += x; } sum"), Ok(10));
+    assert_eq!(engine.eval::<String>("let s = \"\"; let a = [\"a\", \"b\", \"c\"]; for x in a { s += x; } s"),
+               Ok("abc".into()));
+    assert_eq!(engine.eval::<i64>("let n = 0; for x in [] { n += 1; } n"), Ok(0));
+    // Nested arrays are looped over as values
+    assert_eq!(engine.eval::<i64>("let n = 0; for row in [[1, 2], [3]] { for x in row { n += x; } } n"), Ok(6));
+}
+
+#[test]
+fn test_for_array_scope() {
+    let mut engine = Engine::new();
+
+    // The loop variable is gone after the loop, and doesn't pile up in the scope
+    assert!(engine.eval::<i64>("for x in [1, 2] { } x").is_err());
+    assert_eq!(engine.eval::<i64>("let x = 7; for x in [1, 2] { } x"), Ok(7));
+    // Changing the loop variable doesn't change the array
+    assert_eq!(engine.eval::<i64>("let a = [1, 2]; for x in a { x = 10; } a[0] + a[1]"), Ok(3));
+}
+
+#[test]
+fn test_for_array_break() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let n = 0; for x in [1, 2, 3, 4] { if x == 3 { break; } n += x; } n"), Ok(3));
+    assert_eq!(engine.eval::<i64>("for x in [5, 6, 7] { if x > 5 { break x; } }"), Ok(6));
+    assert_eq!(engine.eval::<i64>("fn first(a) { for x in a { return x; } 0 } first([9, 8])"), Ok(9));
+}
+
+#[test]
+fn test_for_non_array() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<()>("for x in \"abc\" { }"),
+               Err(EvalAltResult::ErrorRuntime("Cannot loop over string, only over an array or a range".into())));
+}
@@ -0,0 +1,79 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, ParseError};
+
+#[test]
+fn test_for_range() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let sum = 0; for i in 0..5 { sum += i; } sum"), Ok(10));
+    assert_eq!(engine.eval::<i64>("let sum = 0; let n = 3; for i in n..n * 2 { sum += i; } sum"), Ok(12));
+    assert_eq!(engine.eval::<i64>("let n = 0; for _ in 0..4 { n += 1; } n"), Ok(4));
+    assert_eq!(engine.eval::<i64>("let n = 0; for i in 5..5 { n += 1; } n"), Ok(0));
+    assert_eq!(engine.eval::<i64>("let n = 0; for i in 5..0 { n += 1; } n"), Ok(0));
+
+    // The loop variable only lives for one pass
+    assert_eq!(engine.eval::<i64>("let i = 42; for i in 0..3 { } i"), Ok(42));
+
+    assert_eq!(engine.eval::<i64>("let x = for i in 1..100 { if i * i > 30 { break i; } }; x"), Ok(6));
+}
+
+#[test]
+fn test_for_range_step() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("let s = \"\"; for i in 0..10 step 3 { s += to_string(i); } s"),
+               Ok("0369".to_string()));
+    assert_eq!(engine.eval::<String>("let s = \"\"; for i in 10..0 step -3 { s += to_string(i); } s"),
+               Ok("10741".to_string()));
+    assert_eq!(engine.eval::<i64>("let n = 0; for i in 0..10 step -1 { n += 1; } n"), Ok(0));
+
+    assert_eq!(
+        engine.eval::<i64>("for i in 0..10 step 0 { }"),
+        Err(EvalAltResult::ErrorArithmetic("Range step cannot be zero".into()))
+    );
+}
+
+#[test]
+fn test_for_range_float() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let n = 0; for x in 0.0..1.0 step 0.1 { n += 1; } n"), Ok(10));
+    assert_eq!(engine.eval::<f64>("let sum = 0.0; for x in 1..0 step -0.25 { sum = sum + x; } sum"), Ok(2.5));
+    assert_eq!(engine.eval::<f64>("let last = 0.0; for x in 0..2 step 0.5 { last = x; } last"), Ok(1.5));
+
+    assert_eq!(
+        engine.eval::<i64>("for x in 0.0..1.0 step 0.0 { }"),
+        Err(EvalAltResult::ErrorArithmetic("Range step cannot be zero".into()))
+    );
+    assert_eq!(
+        engine.eval::<i64>("for x in 0..\"a\" { }"),
+        Err(EvalAltResult::ErrorRuntime("Cannot loop over a range of string".into()))
+    );
+}
+
+#[test]
+fn test_for_range_syntax() {
+    let mut engine = Engine::new();
+
+    // Floats next to `..` are still read as numbers
+    assert_eq!(engine.eval::<f64>("let x = 0; for i in 0..2 { x = 1.5; } x"), Ok(1.5));
+    // `step` can still name a variable
+    assert_eq!(engine.eval::<i64>("let step = 2; let n = 0; for i in 0..6 step step { n += 1; } n"), Ok(3));
+
+    assert_eq!(engine.eval::<i64>("for i 0..3 { }"), Err(EvalAltResult::ErrorParsing(ParseError::ForMissingIn)));
+    assert_eq!(engine.eval::<()>("for i in 3 { }"),
+               Err(EvalAltResult::ErrorRuntime("Cannot loop over integer, only over an array or a range".into())));
+}
+
+#[test]
+fn test_for_range_bounds() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let sum = 0; for i in -3..0 { sum += i; } sum"), Ok(-6));
+    // Counting up to the largest integer stops instead of overflowing
+    assert_eq!(engine.eval::<i64>("let n = 0; for i in 9223372036854775805..9223372036854775807 { n += 1; } n"),
+               Ok(2));
+    assert_eq!(engine.eval::<i64>("let n = 0; for i in 9223372036854775800..9223372036854775807 step 5 { n += 1; } n"),
+               Ok(2));
+}
@@ -31,11 +31,7 @@ fn test_get_set() {
     engine.register_get_set("x", TestStruct::get_x, TestStruct::set_x);
     engine.register_fn("new_ts", TestStruct::new);
 
-    if let Ok(result) = engine.eval::<i64>("let a = new_ts(); a.x = 500; a.x") {
-        assert_eq!(result, 500);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("let a = new_ts(); a.x = 500; a.x"), Ok(500));
 }
 
 #[test]
@@ -90,3 +86,105 @@ fn test_big_get_set() {
 
     assert_eq!(engine.eval::<i64>("let a = new_tp(); a.child.x = 500; a.child.x"), Ok(500));
 }
+
+#[test]
+fn test_get_set_same_name_on_different_types() {
+    #[derive(Clone)]
+    struct Player {
+        name: String,
+    }
+
+    #[derive(Clone)]
+    struct Item {
+        name: String,
+        owner: Player,
+    }
+
+    impl Player {
+        fn get_name(&mut self) -> String {
+            format!("player {}", self.name)
+        }
+
+        fn set_name(&mut self, name: String) {
+            self.name = name;
+        }
+
+        fn new() -> Player {
+            Player { name: "Ann".into() }
+        }
+    }
+
+    impl Item {
+        fn get_name(&mut self) -> String {
+            format!("item {}", self.name)
+        }
+
+        fn set_name(&mut self, name: String) {
+            self.name = name.to_uppercase();
+        }
+
+        fn get_owner(&mut self) -> Player {
+            self.owner.clone()
+        }
+
+        fn set_owner(&mut self, owner: Player) {
+            self.owner = owner;
+        }
+
+        fn new() -> Item {
+            Item { name: "sword".into(), owner: Player::new() }
+        }
+    }
+
+    let mut engine = Engine::new();
+
+    engine.register_type::<Player>();
+    engine.register_type::<Item>();
+
+    engine.register_get_set("name", Player::get_name, Player::set_name);
+    engine.register_get_set("name", Item::get_name, Item::set_name);
+    engine.register_get_set("owner", Item::get_owner, Item::set_owner);
+    engine.register_fn("new_player", Player::new);
+    engine.register_fn("new_item", Item::new);
+
+    assert_eq!(engine.eval::<String>("let p = new_player(); p.name"), Ok("player Ann".to_string()));
+    assert_eq!(engine.eval::<String>("let i = new_item(); i.name"), Ok("item sword".to_string()));
+    assert_eq!(engine.eval::<String>("let i = new_item(); i.name = \"axe\"; i.name"),
+               Ok("item AXE".to_string()));
+    assert_eq!(engine.eval::<String>("let i = new_item(); i.owner.name = \"Bo\"; i.owner.name + \" / \" + i.name"),
+               Ok("player Bo / item sword".to_string()));
+}
+
+#[test]
+fn test_get_set_reserved_name() {
+    #[derive(Clone)]
+    struct Token {
+        kind: String,
+    }
+
+    impl Token {
+        fn get_type(&mut self) -> String {
+            self.kind.clone()
+        }
+
+        fn set_type(&mut self, kind: String) {
+            self.kind = kind;
+        }
+
+        fn new() -> Token {
+            Token { kind: "word".into() }
+        }
+    }
+
+    let mut engine = Engine::new();
+
+    engine.register_get_set("type", Token::get_type, Token::set_type);
+    engine.register_get_set("fn", Token::get_type, Token::set_type);
+    engine.register_fn("new_token", Token::new);
+
+    assert_eq!(engine.eval::<String>("let t = new_token(); t.`type`"), Ok("word".to_string()));
+    assert_eq!(engine.eval::<String>("let t = new_token(); t.`fn` = \"number\"; t.`type`"),
+               Ok("number".to_string()));
+    assert_eq!(engine.eval::<i64>("let `if` = 1; `if` + 1"), Ok(2));
+    assert!(engine.eval::<String>("let t = new_token(); t.fn").is_err());
+}
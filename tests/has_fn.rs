@@ -0,0 +1,33 @@
+extern crate rhai;
+
+use rhai::{Engine, RegisterFn};
+
+#[test]
+fn test_has_fn() {
+    let mut engine = Engine::new();
+
+    assert!(engine.has_fn("+", 2));
+    assert!(engine.has_fn("-", 1));
+    assert!(!engine.has_fn("+", 3));
+    assert!(engine.has_fn("to_string", 1));
+    assert!(!engine.has_fn("add_three", 3));
+
+    engine.register_fn("add_three", |a: i64, b: i64, c: i64| a + b + c);
+    assert!(engine.has_fn("add_three", 3));
+    assert!(!engine.has_fn("add_three", 2));
+}
+
+#[test]
+fn test_has_fn_script() {
+    let mut engine = Engine::new();
+
+    engine.consume("fn double(x) { x * 2 }").unwrap();
+
+    assert!(engine.has_fn("double", 1));
+    assert!(!engine.has_fn("double", 0));
+
+    let mut engine = Engine::builder().sandboxed().build();
+
+    engine.consume("fn double(x) { x * 2 }").unwrap();
+    assert!(!engine.has_fn("double", 1));
+}
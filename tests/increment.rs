@@ -6,15 +6,7 @@ use rhai::Engine;
 fn test_increment() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("let x = 1; x += 2; x") {
-        assert_eq!(result, 3);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("let x = 1; x += 2; x"), Ok(3));
 
-    if let Ok(result) = engine.eval::<String>("let s = \"test\"; s += \"ing\"; s") {
-        assert_eq!(result, "testing".to_owned());
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<String>("let s = \"test\"; s += \"ing\"; s"), Ok("testing".to_owned()));
 }
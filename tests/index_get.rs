@@ -0,0 +1,50 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, RegisterFn};
+
+#[derive(Clone)]
+struct Inventory {
+    items: Vec<String>,
+}
+
+#[derive(Clone)]
+struct Player {
+    inventory: Inventory,
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+
+    engine.register_type_name::<Inventory>("Inventory");
+    engine.register_fn("new_player", || Player {
+        inventory: Inventory { items: vec!["sword".into(), "shield".into()] },
+    });
+    engine.register_get("inventory", |p: &mut Player| p.inventory.clone());
+
+    engine
+}
+
+#[test]
+fn test_index_get() {
+    let mut engine = engine();
+
+    engine.register_index_get(|inv: &mut Inventory, i: i64| inv.items[i as usize].clone());
+    engine.register_index_get(|inv: &mut Inventory, name: String| inv.items.contains(&name));
+
+    assert_eq!(engine.eval::<String>("let p = new_player(); p.inventory[1]"), Ok("shield".to_string()));
+    assert_eq!(engine.eval::<String>("let p = new_player(); let inv = p.inventory; inv[0]"), Ok("sword".to_string()));
+    assert_eq!(engine.eval::<bool>("let p = new_player(); p.inventory[\"shield\"]"), Ok(true));
+
+    // Arrays are still indexed the usual way
+    assert_eq!(engine.eval::<i64>("let a = [1, 2]; a[1]"), Ok(2));
+}
+
+#[test]
+fn test_index_get_missing() {
+    let mut engine = engine();
+
+    assert_eq!(
+        engine.eval::<String>("let p = new_player(); p.inventory[1]"),
+        Err(EvalAltResult::ErrorIndexingType("Inventory".to_string()))
+    );
+}
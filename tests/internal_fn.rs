@@ -1,32 +1,45 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{Engine, EvalAltResult, ParseError};
 
 #[test]
 fn test_internal_fn() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("fn addme(a, b) { a+b } addme(3, 4)") {
-        assert_eq!(result, 7);
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval::<i64>("fn bob() { return 4; 5 } bob()") {
-        assert_eq!(result, 4);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("fn addme(a, b) { a+b } addme(3, 4)"), Ok(7));
+
+    assert_eq!(engine.eval::<i64>("fn bob() { return 4; 5 } bob()"), Ok(4));
 }
 
 #[test]
 fn test_big_internal_fn() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("fn mathme(a, b, c, d, e, f) { a - b * c + d * e - f \
-                                            } mathme(100, 5, 2, 9, 6, 32)") {
-        assert_eq!(result, 112);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("fn mathme(a, b, c, d, e, f) { a - b * c + d * e - f \
+                                            } mathme(100, 5, 2, 9, 6, 32)"), Ok(112));
+}
+
+#[test]
+fn test_internal_fn_mutual_recursion() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<bool>("fn is_even(n) { if n == 0 { return true; } is_odd(n - 1) } \
+                                    fn is_odd(n) { if n == 0 { return false; } is_even(n - 1) } \
+                                    is_even(10)"),
+               Ok(true));
+    assert_eq!(engine.eval::<bool>("is_odd(7); \
+                                    fn is_odd(n) { if n == 0 { return false; } is_even(n - 1) } \
+                                    fn is_even(n) { if n == 0 { return true; } is_odd(n - 1) }"),
+               Ok(true));
+}
+
+#[test]
+fn test_internal_fn_duplicate_params() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("fn f(x, y, x) { x } f(1, 2, 3)"),
+               Err(EvalAltResult::ErrorParsing(ParseError::FnDuplicateParam("x".into()))));
+    assert_eq!(ParseError::FnDuplicateParam("x".into()).to_string(),
+               "Function declaration has the same parameter twice: x");
+    assert_eq!(engine.eval::<i64>("fn f(_, y, _) { y } f(1, 2, 3)"), Ok(2));
 }
@@ -0,0 +1,24 @@
+extern crate rhai;
+
+use std::collections::HashMap;
+
+use rhai::{Any, Engine, Scope, ScopeExt};
+
+#[test]
+fn test_len() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("len(\"abc\")"), Ok(3));
+    assert_eq!(engine.eval::<i64>("len(\"héllo\")"), Ok(5));
+    assert_eq!(engine.eval::<i64>("len([1, 2])"), Ok(2));
+    assert_eq!(engine.eval::<i64>("len([])"), Ok(0));
+    assert_eq!(engine.eval::<i64>("let s = \"ab\"; s.len() + len([s, s, s])"), Ok(5));
+
+    let mut map: HashMap<String, Box<dyn Any>> = HashMap::new();
+    map.insert("a".into(), Box::new(1i64));
+
+    let mut scope = Scope::new();
+    scope.push_value("m", map);
+
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "len(m)"), Ok(1));
+}
@@ -0,0 +1,24 @@
+extern crate rhai;
+
+use rhai::{Engine, LintWarning};
+
+#[test]
+fn test_lint_unreachable() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.lint("fn f(x) { return x; x + 1 } f(1)"),
+               Ok(vec![LintWarning::UnreachableCode(Some("f".to_string()))]));
+    assert_eq!(engine.lint("loop { break; let x = 1; }"),
+               Ok(vec![LintWarning::UnreachableCode(None)]));
+    assert_eq!(engine.lint("let x = { return 1; 2 };"),
+               Ok(vec![LintWarning::UnreachableCode(None)]));
+}
+
+#[test]
+fn test_lint_clean() {
+    let engine = Engine::new();
+
+    assert_eq!(engine.lint("fn f(x) { if x > 1 { return x; } x + 1 } f(1)"), Ok(vec![]));
+    assert_eq!(engine.lint("let x = 1; while x < 10 { x = x + 1; if x == 5 { break; } }"),
+               Ok(vec![]));
+}
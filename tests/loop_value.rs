@@ -0,0 +1,67 @@
+extern crate rhai;
+
+use rhai::{Any, Engine, EvalAltResult};
+
+#[test]
+fn test_loop_break_value() {
+    let mut engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<i64>("let x = 1; let first = loop { if x * x > 50 { break x; } x += 1; }; first"),
+        Ok(8)
+    );
+    assert_eq!(engine.eval::<i64>("let i = 0; let x = while true { i += 1; if i == 3 { break i * 10; } }; x"), Ok(30));
+    assert_eq!(engine.eval::<i64>("let x = loop { break 2 + 3; } * 2; x"), Ok(10));
+
+    // As a statement, the loop is still the value of the script
+    assert_eq!(engine.eval::<i64>("loop { break 7; }"), Ok(7));
+
+    // Only the innermost loop is broken
+    assert_eq!(
+        engine.eval::<i64>("let n = 0; let x = loop { let y = loop { break 4; }; n += y; if n > 10 { break n; } }; x"),
+        Ok(12)
+    );
+}
+
+#[test]
+fn test_loop_no_break_value() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<bool>("let i = 0; let x = while i < 3 { i += 1; }; x == ()"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let x = while false { break 1; }; x == ()"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let x = loop { break; }; x == ()"), Ok(true));
+}
+
+#[test]
+fn test_break_outside_loop() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = 1; break x + 1;"),
+               Err(EvalAltResult::ErrorRuntime("Cannot break outside of a loop".into())));
+    assert_eq!(engine.eval::<i64>("if true { continue; } 1"),
+               Err(EvalAltResult::ErrorRuntime("Cannot continue outside of a loop".into())));
+}
+
+#[test]
+fn test_break_value_debug() {
+    #![allow(bare_trait_objects)]
+
+    let err = EvalAltResult::LoopBreak(Box::new(2i64) as Box<Any>);
+    assert_eq!(format!("{:?}", err), "LoopBreak(Any(i64))");
+}
+
+#[test]
+fn test_return_value() {
+    #![allow(bare_trait_objects)]
+
+    let ret = EvalAltResult::Return(Box::new(vec![Box::new(1i64) as Box<Any>]));
+
+    match ret {
+        EvalAltResult::Return(v) => {
+            let arr = v.downcast_ref::<Vec<Box<Any>>>().unwrap();
+            assert_eq!(arr.len(), 1);
+            assert_eq!(arr[0].downcast_ref::<i64>(), Some(&1));
+        }
+        _ => panic!("expected Return"),
+    }
+}
@@ -0,0 +1,48 @@
+extern crate rhai;
+
+use std::collections::HashMap;
+
+use rhai::{Any, Engine, Scope, ScopeExt};
+
+fn new_scope() -> Scope {
+    let mut map: HashMap<String, Box<dyn Any>> = HashMap::new();
+    map.insert("a".into(), Box::new(1i64));
+    map.insert("b".into(), Box::new(2i64));
+    map.insert("c".into(), Box::new(3i64));
+
+    let mut scope = Scope::new();
+    scope.push_value("m", map);
+    scope
+}
+
+#[test]
+fn test_map_keys_values() {
+    let mut engine = Engine::new();
+    let mut scope = new_scope();
+
+    let keys = engine.eval_with_scope::<Vec<Box<dyn Any>>>(&mut scope, "keys(m)").unwrap();
+    let values = engine.eval_with_scope::<Vec<Box<dyn Any>>>(&mut scope, "m.values()").unwrap();
+
+    assert_eq!(keys.len(), 3);
+
+    // Keys and values come out in matching order
+    for (k, v) in keys.iter().zip(values.iter()) {
+        let k = k.downcast_ref::<String>().unwrap();
+        let v = *v.downcast_ref::<i64>().unwrap();
+        assert_eq!(v, (k.as_bytes()[0] - b'a') as i64 + 1);
+    }
+}
+
+#[test]
+fn test_map_has_key_remove() {
+    let mut engine = Engine::new();
+    let mut scope = new_scope();
+
+    assert_eq!(engine.eval_with_scope::<bool>(&mut scope, "has_key(m, \"a\")"), Ok(true));
+    assert_eq!(engine.eval_with_scope::<bool>(&mut scope, "m.has_key(\"z\")"), Ok(false));
+
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "m.remove(\"b\")"), Ok(2));
+    assert_eq!(engine.eval_with_scope::<bool>(&mut scope, "m.has_key(\"b\")"), Ok(false));
+    assert_eq!(engine.eval_with_scope::<bool>(&mut scope, "m.remove(\"b\") == ()"), Ok(true));
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "sum(values(m))"), Ok(4));
+}
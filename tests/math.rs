@@ -0,0 +1,24 @@
+extern crate rhai;
+
+use rhai::Engine;
+
+#[test]
+fn test_clamp() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("clamp(5, 0, 10)"), Ok(5));
+    assert_eq!(engine.eval::<i64>("clamp(15, 0, 10)"), Ok(10));
+    assert_eq!(engine.eval::<i64>("let x = 0 - 5; clamp(x, 0, 10)"), Ok(0));
+    assert_eq!(engine.eval::<i64>("clamp(5, 10, 0)"), Ok(10));
+    assert_eq!(engine.eval::<f64>("clamp(1.5, 0.0, 1.0)"), Ok(1.0));
+    assert_eq!(engine.eval::<f64>("clamp(0.5, 0.0, 1.0)"), Ok(0.5));
+}
+
+#[test]
+fn test_lerp() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<f64>("lerp(0.0, 10.0, 0.25)"), Ok(2.5));
+    assert_eq!(engine.eval::<f64>("lerp(2.0, 4.0, 0.0)"), Ok(2.0));
+    assert_eq!(engine.eval::<f64>("lerp(2.0, 4.0, 1.0)"), Ok(4.0));
+}
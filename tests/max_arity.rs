@@ -0,0 +1,47 @@
+extern crate rhai;
+
+use rhai::{Engine, RegisterFn};
+
+#[test]
+fn test_max_arity_method_call() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("weigh", |a: &mut i64, b: i64, c: i64, d: i64, e: i64, f: i64| *a * b + c + d + e + f);
+
+    assert_eq!(engine.eval::<i64>("let x = 10; x.weigh(2, 1, 1, 1, 1)"), Ok(24));
+    assert_eq!(engine.eval::<i64>("fn mathme(a, b, c, d, e, f) { a - b * c + d * e - f } \
+                                   let x = 100; x.mathme(5, 2, 9, 6, 32)"),
+               Ok(112));
+}
+
+#[test]
+fn test_script_fn_many_params() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("fn eight(a, b, c, d, e, f, g, h) { a + b + c + d + e + f + g + h } \
+                                   eight(1, 2, 3, 4, 5, 6, 7, 8)"),
+               Ok(36));
+    assert_eq!(engine.eval::<i64>("fn seven(a, b, c, d, e, f, g) { g - a } let x = 1; x.seven(2, 3, 4, 5, 6, 10)"),
+               Ok(9));
+}
+
+#[test]
+fn test_native_fn_eight_args() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("spawn_entity", |kind: String, x: i64, y: i64, z: i64, hp: i64, speed: f64, team: i64, alive: bool| {
+        format!("{} at ({}, {}, {}) hp={} speed={} team={} alive={}", kind, x, y, z, hp, speed, team, alive)
+    });
+    engine.register_fn("sum8", |a: &mut i64, b: i64, c: i64, d: i64, e: i64, f: i64, g: i64, h: i64| {
+        *a + b + c + d + e + f + g + h
+    });
+
+    assert_eq!(engine.eval::<String>("spawn_entity(\"orc\", 1, 2, 3, 40, 1.5, 2, true)"),
+               Ok("orc at (1, 2, 3) hp=40 speed=1.5 team=2 alive=true".to_string()));
+    assert_eq!(engine.eval::<i64>("let x = 1; x.sum8(2, 3, 4, 5, 6, 7, 8)"), Ok(36));
+
+    let mut args = (1i64, 2i64, 3i64, 4i64, 5i64, 6i64, 7i64, 8i64);
+    assert_eq!(engine.call_fn::<_, _, i64>("sum8", (&mut args.0, &mut args.1, &mut args.2, &mut args.3,
+                                                   &mut args.4, &mut args.5, &mut args.6, &mut args.7)),
+               Ok(36));
+}
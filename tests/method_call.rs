@@ -27,10 +27,43 @@ fn test_method_call() {
     engine.register_fn("update", TestStruct::update);
     engine.register_fn("new_ts", TestStruct::new);
 
-    if let Ok(result) = engine.eval::<TestStruct>("let x = new_ts(); x.update(); x") {
-        assert_eq!(result.x, 1001);
-    } else {
-        assert!(false);
+    let result = engine.eval::<TestStruct>("let x = new_ts(); x.update(); x").unwrap();
+    assert_eq!(result.x, 1001);
+}
+
+#[test]
+fn test_method_call_write_back() {
+    #[derive(Clone)]
+    struct TestStruct {
+        x: i64,
     }
 
+    impl TestStruct {
+        fn add(&mut self, n: i64) {
+            self.x += n;
+        }
+
+        fn get_x(&mut self) -> i64 {
+            self.x
+        }
+
+        fn new() -> TestStruct {
+            TestStruct { x: 1 }
+        }
+    }
+
+    let mut engine = Engine::new();
+
+    engine.register_type::<TestStruct>();
+
+    engine.register_get("x", TestStruct::get_x);
+    engine.register_fn("add", TestStruct::add);
+    engine.register_fn("new_ts", TestStruct::new);
+
+    // The argument declares a local named like the receiver, which must not get the update
+    assert_eq!(engine.eval::<i64>("let t = new_ts(); t.add({ let t = 5; t }); t.x"), Ok(6));
+    assert_eq!(engine.eval::<i64>("let a = [new_ts()]; a[0].add({ let a = 10; a }); a[0].x"), Ok(11));
+
+    // The argument replaces the array the receiver came from
+    assert_eq!(engine.eval::<bool>("let a = [new_ts()]; a[0].add({ a = []; 1 }); a == []"), Ok(true));
 }
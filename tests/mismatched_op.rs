@@ -1,6 +1,6 @@
 extern crate rhai;
 
-use rhai::{Engine, EvalAltResult};
+use rhai::{Engine, EvalAltResult, Position};
 
 #[test]
 fn test_mismatched_op() {
@@ -8,6 +8,6 @@ fn test_mismatched_op() {
 
     assert_eq!(
         engine.eval::<i64>("60 + \"hello\""),
-        Err(EvalAltResult::ErrorFunctionNotFound("+ (integer,string)".into()))
+        Err(EvalAltResult::ErrorFunctionNotFound("+ (integer,string)".into(), Position { line: 1, column: 4 }))
     );
 }
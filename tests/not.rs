@@ -6,10 +6,29 @@ use rhai::Engine;
 fn test_not() {
 	let mut engine = Engine::new();
 
-	assert_eq!(engine.eval::<bool>("let not_true = !true; not_true").unwrap(), false);
+	assert_eq!(engine.eval::<bool>("let not_true = !true; not_true"), Ok(false));
 
-	assert_eq!(engine.eval::<bool>("fn not(x) { !x } not(false)").unwrap(), true);
+	assert_eq!(engine.eval::<bool>("fn not(x) { !x } not(false)"), Ok(true));
 
 	// TODO - do we allow stacking unary operators directly? e.g '!!!!!!!true'
-	assert_eq!(engine.eval::<bool>("!(!(!(!(true))))").unwrap(), true)
+	assert_eq!(engine.eval::<bool>("!(!(!(!(true))))"), Ok(true))
+}
+
+#[test]
+fn test_bitwise_not() {
+	let mut engine = Engine::new();
+
+	assert_eq!(engine.eval::<bool>("~0 == -1"), Ok(true));
+	assert_eq!(engine.eval::<i64>("let x = 5; ~x"), Ok(-6));
+	assert_eq!(engine.eval::<i64>("let x = 2; x ~ 3 + ~1"), Ok(6));
+	assert!(engine.eval::<i64>("!5").is_err());
+	assert!(engine.eval::<bool>("~true").is_err());
+}
+
+#[test]
+fn test_unary_at_statement_start() {
+	let mut engine = Engine::new();
+
+	assert_eq!(engine.eval::<i64>("-5"), Ok(-5));
+	assert_eq!(engine.eval::<i64>("let x = 1; -x"), Ok(-1));
 }
@@ -1,65 +1,73 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{tokenize, Engine, EvalAltResult, LexError, Token};
 
 #[test]
 fn test_number_literal() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("65") {
-        assert_eq!(result, 65);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("65"), Ok(65));
+}
+
+#[test]
+fn test_float_exponent_literal() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<f64>("6.022e23"), Ok(6.022e23));
+    assert_eq!(engine.eval::<f64>("1.5e-3"), Ok(1.5e-3));
+    assert_eq!(engine.eval::<f64>("2E+2"), Ok(200.0));
+    assert_eq!(engine.eval::<f64>("1e5"), Ok(100_000.0));
+    assert_eq!(engine.eval::<f64>(".5e1"), Ok(5.0));
+    assert_eq!(engine.eval::<f64>("-2.5e2 + 1.0"), Ok(-249.0));
+
+    assert_eq!(tokenize("1e")[0].0, Token::LexErr(LexError::MalformedNumber));
+    assert_eq!(tokenize("1e+")[0].0, Token::LexErr(LexError::MalformedNumber));
+    assert_eq!(tokenize("1.5E-x")[0].0, Token::LexErr(LexError::MalformedNumber));
+    // Hex digits aren't exponents
+    assert_eq!(engine.eval::<i64>("0x1e5"), Ok(0x1e5));
 }
 
 #[test]
 fn test_hex_literal() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("let x = 0xf; x") {
-        assert_eq!(result, 15);
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval::<i64>("let x = 0xff; x") {
-        assert_eq!(result, 255);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("let x = 0xf; x"), Ok(15));
+
+    assert_eq!(engine.eval::<i64>("let x = 0xff; x"), Ok(255));
 }
 
 #[test]
 fn test_octal_literal() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("let x = 0o77; x") {
-        assert_eq!(result, 63);
-    } else {
-        assert!(false)
-    }
-
-    if let Ok(result) = engine.eval::<i64>("let x = 0o1234; x") {
-        assert_eq!(result, 668);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("let x = 0o77; x"), Ok(63));
+
+    assert_eq!(engine.eval::<i64>("let x = 0o1234; x"), Ok(668));
 }
 
 #[test]
 fn test_binary_literal() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<i64>("let x = 0b1111; x") {
-        assert_eq!(result, 15);
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval::<i64>("let x = 0b0011_1100_1010_0101; x") {
-        assert_eq!(result, 15525);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<i64>("let x = 0b1111; x"), Ok(15));
+
+    assert_eq!(engine.eval::<i64>("let x = 0b0011_1100_1010_0101; x"), Ok(15525));
+}
+
+#[test]
+fn test_to_radix_strings() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("to_hex(255)"), Ok("ff".to_string()));
+    assert_eq!(engine.eval::<String>("to_hex(0xABC)"), Ok("abc".to_string()));
+    assert_eq!(engine.eval::<String>("to_octal(8)"), Ok("10".to_string()));
+    assert_eq!(engine.eval::<String>("to_binary(5)"), Ok("101".to_string()));
+    assert_eq!(engine.eval::<String>("to_binary(0)"), Ok("0".to_string()));
+    assert_eq!(engine.eval::<String>("to_hex(-1)"), Ok("ffffffffffffffff".to_string()));
+
+    assert_eq!(engine.eval::<String>("to_hex(255, 4)"), Ok("00ff".to_string()));
+    assert_eq!(engine.eval::<String>("to_binary(5, 8)"), Ok("00000101".to_string()));
+    assert_eq!(engine.eval::<String>("to_hex(4096, 2)"), Ok("1000".to_string()));
+    assert_eq!(engine.eval::<String>("to_octal(1, -1)"),
+               Err(EvalAltResult::ErrorRuntime("Cannot pad a number to -1 digits".to_string())));
 }
@@ -0,0 +1,29 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult};
+
+#[test]
+fn test_parse_int() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("parse_int(\"42\")"), Ok(42));
+    assert_eq!(engine.eval::<i64>("parse_int(\" -17 \")"), Ok(-17));
+    assert_eq!(engine.eval::<i64>("parse_int(\"ff\", 16)"), Ok(255));
+    assert_eq!(engine.eval::<i64>("parse_int(\"101\", 2)"), Ok(5));
+
+    assert_eq!(engine.eval::<i64>("parse_int(\"abc\")"),
+               Err(EvalAltResult::ErrorRuntime("Cannot parse 'abc' as an integer".to_string())));
+    assert_eq!(engine.eval::<i64>("parse_int(\"1\", 99)"),
+               Err(EvalAltResult::ErrorRuntime("Invalid radix: 99".to_string())));
+}
+
+#[test]
+fn test_parse_float() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<f64>("parse_float(\"1.5\")"), Ok(1.5));
+    assert_eq!(engine.eval::<f64>("parse_float(\"3\")"), Ok(3.0));
+
+    assert_eq!(engine.eval::<f64>("parse_float(\"1.2.3\")"),
+               Err(EvalAltResult::ErrorRuntime("Cannot parse '1.2.3' as a float".to_string())));
+}
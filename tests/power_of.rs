@@ -1,6 +1,6 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{Engine, EvalAltResult, RegisterFn};
 
 #[test]
 fn test_power_of() {
@@ -27,3 +27,33 @@ fn test_power_of_equals() {
     assert_eq!(engine.eval::<f64>("let x = -2.0; x ~= -2; x").unwrap(), 0.25_f64);
     assert_eq!(engine.eval::<i64>("let x =4; x ~= 3; x").unwrap(), 64);
 }
+
+#[test]
+fn test_power_of_integer_edges() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("0 ~ 0"), Ok(1));
+    assert_eq!(engine.eval::<i64>("5 ~ 0"), Ok(1));
+    assert_eq!(engine.eval::<i64>("2 ~ 62"), Ok(1 << 62));
+    assert_eq!(
+        engine.eval::<i64>("2 ~ -1"),
+        Err(EvalAltResult::ErrorArithmetic("Cannot raise an integer to a negative power: -1".into()))
+    );
+    assert_eq!(engine.eval::<i64>("2 ~ 63"), Err(EvalAltResult::ErrorArithmetic("Power overflow: 2 ~ 63".into())));
+}
+
+#[test]
+fn test_power_of_host_types() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("int32", |x: i64| x as i32);
+    engine.register_fn("uint32", |x: i64| x as u32);
+    engine.register_fn("uint64", |x: i64| x as u64);
+    engine.register_fn("float32", |x: f64| x as f32);
+
+    assert_eq!(engine.eval::<i32>("int32(-3) ~ int32(3)"), Ok(-27));
+    assert_eq!(engine.eval::<u32>("uint32(3) ~ uint32(4)"), Ok(81));
+    assert_eq!(engine.eval::<u64>("uint64(2) ~ uint64(40)"), Ok(1 << 40));
+    assert_eq!(engine.eval::<f32>("float32(4.0) ~ float32(0.5)"), Ok(2.0));
+    assert!(engine.eval::<u32>("uint32(2) ~ uint32(32)").is_err());
+}
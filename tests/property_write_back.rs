@@ -0,0 +1,109 @@
+#![allow(bare_trait_objects)]
+
+extern crate rhai;
+
+use rhai::{Any, Engine, RegisterFn};
+
+#[derive(Clone)]
+struct Counter {
+    n: i64,
+}
+
+#[derive(Clone)]
+struct Bag {
+    list: Vec<Box<Any>>,
+    counter: Counter,
+    list_sets: i64,
+}
+
+impl Bag {
+    fn get_list(&mut self) -> Vec<Box<Any>> {
+        self.list.clone()
+    }
+
+    fn set_list(&mut self, list: Vec<Box<Any>>) {
+        self.list = list;
+        self.list_sets += 1;
+    }
+
+    fn get_counter(&mut self) -> Counter {
+        self.counter.clone()
+    }
+
+    fn set_counter(&mut self, counter: Counter) {
+        self.counter = counter;
+    }
+}
+
+#[derive(Clone)]
+struct Holder {
+    bag: Bag,
+}
+
+fn new_engine() -> Engine {
+    let mut engine = Engine::new();
+
+    engine.register_get_set("list", Bag::get_list, Bag::set_list);
+    engine.register_get_set("counter", Bag::get_counter, Bag::set_counter);
+    engine.register_get_set("bag", |h: &mut Holder| h.bag.clone(), |h: &mut Holder, bag: Bag| h.bag = bag);
+    engine.register_get("list_sets", |b: &mut Bag| b.list_sets);
+    engine.register_get("n", |c: &mut Counter| c.n);
+    engine.register_fn("bump", |c: &mut Counter| c.n += 1);
+    engine.register_fn("push", |list: &mut Vec<Box<Any>>, x: i64| list.push(Box::new(x)));
+    engine.register_fn("new_bag", || Bag { list: Vec::new(), counter: Counter { n: 0 }, list_sets: 0 });
+    engine.register_fn("new_holder", || Holder { bag: Bag { list: Vec::new(), counter: Counter { n: 0 }, list_sets: 0 } });
+
+    engine
+}
+
+#[test]
+fn test_method_on_property_writes_back() {
+    let mut engine = new_engine();
+
+    assert_eq!(engine.eval::<i64>("let b = new_bag(); b.list.push(1); b.list.push(2); len(b.list)"), Ok(2));
+    assert_eq!(engine.eval::<i64>("let b = new_bag(); b.counter.bump(); b.counter.bump(); b.counter.n"), Ok(2));
+    assert_eq!(engine.eval::<i64>("let b = [new_bag()]; b[0].list.push(5); len(b[0].list)"), Ok(1));
+}
+
+#[test]
+fn test_method_on_indexed_property_writes_back() {
+    let mut engine = new_engine();
+
+    assert_eq!(engine.eval::<i64>("let b = new_bag(); b.list = [[1], [2]]; b.list[1].push(3); len(b.list[1]) * 10 + len(b.list[0])"),
+               Ok(21));
+    assert_eq!(engine.eval::<i64>("let h = new_holder(); h.bag.list = [[1]]; h.bag.list[0].push(3); len(h.bag.list[0])"),
+               Ok(2));
+}
+
+#[test]
+fn test_reading_method_does_not_write_back() {
+    let mut engine = new_engine();
+
+    // Only a method which takes the property by `&mut` can change it
+    assert_eq!(engine.eval::<i64>("let b = new_bag(); b.list.len(); b.list.len(); b.list_sets"), Ok(0));
+    assert_eq!(engine.eval::<i64>("let b = new_bag(); b.list.push(1); b.list_sets"), Ok(1));
+
+    // Script functions get a copy of the property, so they can't either
+    assert_eq!(engine.eval::<i64>("fn size(l) { len(l) } let b = new_bag(); b.list.size(); b.list_sets"), Ok(0));
+}
+
+#[test]
+fn test_method_on_nested_property_writes_back() {
+    let mut engine = new_engine();
+
+    assert_eq!(engine.eval::<i64>("let h = new_holder(); h.bag.list.push(1); h.bag.counter.bump(); \
+                                   len(h.bag.list) + h.bag.counter.n"),
+               Ok(2));
+}
+
+#[test]
+fn test_read_only_property_is_not_written_back() {
+    let mut engine = Engine::new();
+
+    engine.register_get("counter", |b: &mut Bag| b.counter.clone());
+    engine.register_get("n", |c: &mut Counter| c.n);
+    engine.register_fn("bump", |c: &mut Counter| c.n += 1);
+    engine.register_fn("new_bag", || Bag { list: Vec::new(), counter: Counter { n: 0 }, list_sets: 0 });
+
+    assert_eq!(engine.eval::<i64>("let b = new_bag(); b.counter.bump(); b.counter.n"), Ok(0));
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "rand")]
+
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult};
+
+#[test]
+fn test_rand_range() {
+    let mut engine = Engine::new();
+
+    for _ in 0..100 {
+        let x = engine.eval::<f64>("rand()").unwrap();
+        assert!((0.0..1.0).contains(&x));
+
+        let n = engine.eval::<i64>("rand_int(1, 6)").unwrap();
+        assert!((1..=6).contains(&n));
+    }
+
+    assert_eq!(engine.eval::<i64>("rand_int(3, 3)"), Ok(3));
+    assert!(engine.eval::<i64>("rand_int(-9223372036854775807 - 1, 9223372036854775807)").is_ok());
+    assert_eq!(engine.eval::<i64>("rand_int(2, 1)"),
+               Err(EvalAltResult::ErrorRuntime("Cannot pick a number from 2 to 1".to_string())));
+}
+
+#[test]
+fn test_srand_is_reproducible() {
+    let mut engine = Engine::new();
+    let script = "srand(42); to_string([rand_int(0, 1000), rand_int(0, 1000), rand()])";
+
+    let first = engine.eval::<String>(script).unwrap();
+    let second = engine.eval::<String>(script).unwrap();
+    assert_eq!(first, second);
+
+    engine.seed_rng(7);
+    let a = engine.eval::<i64>("rand_int(0, 1000000)").unwrap();
+    engine.seed_rng(7);
+    assert_eq!(engine.eval::<i64>("rand_int(0, 1000000)"), Ok(a));
+    assert_eq!(engine.eval::<i64>("srand(7); rand_int(0, 1000000)"), Ok(a));
+}
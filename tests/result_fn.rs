@@ -0,0 +1,82 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, RegisterFn, RegisterResultFn};
+
+fn parse_int(s: String) -> Result<i64, EvalAltResult> {
+    s.trim()
+        .parse()
+        .map_err(|_| EvalAltResult::ErrorRuntime(format!("Not a number: {}", s)))
+}
+
+#[test]
+fn test_result_fn() {
+    let mut engine = Engine::new();
+
+    engine.register_result_fn("parse_int", parse_int);
+
+    assert_eq!(engine.eval::<i64>("parse_int(\" 42 \") + 1"), Ok(43));
+    assert_eq!(engine.eval::<i64>("parse_int(\"abc\") + 1"),
+               Err(EvalAltResult::ErrorRuntime("Not a number: abc".into())));
+
+    // The error stops the script where the function was called
+    assert_eq!(engine.eval::<i64>("let x = 1; x = parse_int(\"?\"); x = 2; x"),
+               Err(EvalAltResult::ErrorRuntime("Not a number: ?".into())));
+}
+
+#[test]
+fn test_result_fn_call_fn() {
+    let mut engine = Engine::new();
+
+    engine.register_result_fn("checked_add", |x: i64, y: i64| {
+        x.checked_add(y).ok_or_else(|| EvalAltResult::ErrorArithmetic("Addition overflow".into()))
+    });
+
+    assert_eq!(engine.call_fn::<_, _, i64>("checked_add", (&mut 1i64, &mut 2i64)), Ok(3));
+    let mut max = i64::MAX;
+    assert_eq!(engine.call_fn::<_, _, i64>("checked_add", (&mut max, &mut 1i64)),
+               Err(EvalAltResult::ErrorArithmetic("Addition overflow".into())));
+}
+
+#[test]
+fn test_result_fn_method() {
+    #[derive(Clone)]
+    struct Counter(i64);
+
+    let mut engine = Engine::new();
+
+    engine.register_type::<Counter>();
+    engine.register_fn("counter", || Counter(0));
+    engine.register_result_fn("take", |c: &mut Counter, n: i64| {
+        if n > c.0 {
+            Err(EvalAltResult::ErrorRuntime(format!("Cannot take {} from {}", n, c.0)))
+        } else {
+            c.0 -= n;
+            Ok(c.0)
+        }
+    });
+    engine.register_fn("add", |c: &mut Counter, n: i64| c.0 += n);
+
+    assert_eq!(engine.eval::<i64>("let c = counter(); c.add(5); c.take(2)"), Ok(3));
+    assert_eq!(engine.eval::<i64>("let c = counter(); c.take(1)"),
+               Err(EvalAltResult::ErrorRuntime("Cannot take 1 from 0".into())));
+}
+
+#[test]
+fn test_result_fn_error_not_masked() {
+    let mut engine = Engine::new();
+
+    // Other overloads of the same name don't hide the error of the one which ran
+    engine.register_result_fn("load", |_: String| -> Result<i64, EvalAltResult> {
+        Err(EvalAltResult::ErrorRuntime("File not found".into()))
+    });
+    engine.register_fn("load", |x: i64| x);
+    engine.register_fn("load", |x: bool| x);
+
+    assert_eq!(engine.eval::<i64>("load(1)"), Ok(1));
+    assert_eq!(engine.eval::<i64>("load(\"a.txt\")"),
+               Err(EvalAltResult::ErrorRuntime("File not found".into())));
+
+    // Nor does calling it from a script function
+    assert_eq!(engine.eval::<i64>("fn f(name) { load(name) + 1 } f(\"a.txt\")"),
+               Err(EvalAltResult::ErrorRuntime("File not found".into())));
+}
@@ -0,0 +1,30 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult};
+
+#[test]
+fn test_return_in_loop() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("fn f() { let i = 0; while true { i += 1; if i == 3 { return i * 10; } } 0 } f()"),
+               Ok(30));
+    assert_eq!(engine.eval::<i64>("fn f() { loop { { if true { return 7; } } } } f() + 1"), Ok(8));
+    assert_eq!(engine.eval::<i64>("fn f() { for i in 0..10 { if i == 4 { return i; } } -1 } f()"), Ok(4));
+    assert_eq!(engine.eval::<i64>("fn f() { loop { loop { return 5; } } } f()"), Ok(5));
+
+    // Only the function returns, not the loop it was called from
+    assert_eq!(engine.eval::<i64>("fn f(x) { loop { return x; } }
+                                   let total = 0;
+                                   for i in 1..4 { total += f(i); }
+                                   total"),
+               Ok(6));
+}
+
+#[test]
+fn test_break_stays_in_function() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("fn f() { break; } let i = 0; loop { i += 1; f(); if i == 3 { break; } } i"),
+               Err(EvalAltResult::ErrorRuntime("Cannot break out of function f, only out of a loop".to_string())));
+    assert_eq!(engine.eval::<i64>("fn f() { let n = 0; loop { n += 1; if n == 2 { break; } } n } f()"), Ok(2));
+}
@@ -0,0 +1,56 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, ParseError, Position};
+
+#[test]
+fn test_optional_final_semicolon() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("40 + 2"), Ok(42));
+    assert_eq!(engine.eval::<i64>("let x = 40; x + 2"), Ok(42));
+    assert_eq!(engine.eval::<()>("let x = 5"), Ok(()));
+    assert_eq!(engine.eval::<i64>("let x = { let y = 1; y + 1 }; x"), Ok(2));
+}
+
+#[test]
+fn test_blocks_need_no_semicolon() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = 1; if x > 0 { x = 2 } x"), Ok(2));
+    assert_eq!(engine.eval::<i64>("let x = 1; while x < 5 { x += 1 } { x += 1 } x"), Ok(6));
+    assert_eq!(engine.eval::<i64>("fn f() { 1 } f()"), Ok(1));
+}
+
+#[test]
+fn test_missing_semicolon() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = 5 x"),
+               Err(EvalAltResult::ErrorParsing(ParseError::MissingSemicolon(Position { line: 1, column: 11 }))));
+    assert_eq!(engine.eval::<i64>("1 + 2 3"),
+               Err(EvalAltResult::ErrorParsing(ParseError::MissingSemicolon(Position { line: 1, column: 7 }))));
+    assert_eq!(engine.eval::<i64>("if true {\n  let x = 1\n  x\n}"),
+               Err(EvalAltResult::ErrorParsing(ParseError::MissingSemicolon(Position { line: 3, column: 3 }))));
+
+    let err = engine.eval::<i64>("let x = 5 x").unwrap_err();
+    assert_eq!(err.position(), Some(Position { line: 1, column: 11 }));
+    assert_eq!(err.to_string(), "Syntax error at line 1, col 11: Expected ';' between statements");
+    assert_eq!(err.display_with_source("let x = 5 x"),
+               "Syntax error at line 1, col 11: Expected ';' between statements\n1 | let x = 5 x\n  |           ^");
+}
+
+#[test]
+fn test_trailing_input() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("(1 + 2))"),
+               Err(EvalAltResult::ErrorParsing(ParseError::TrailingInput)));
+    assert_eq!(engine.eval::<i64>("let x = [1]; x[0]]"),
+               Err(EvalAltResult::ErrorParsing(ParseError::TrailingInput)));
+    assert_eq!(engine.eval::<i64>("let x = 1; }"),
+               Err(EvalAltResult::ErrorParsing(ParseError::TrailingInput)));
+    assert_eq!(engine.eval::<i64>("if true { 1 } }"),
+               Err(EvalAltResult::ErrorParsing(ParseError::TrailingInput)));
+    assert_eq!(engine.eval::<i64>("1; )"),
+               Err(EvalAltResult::ErrorParsing(ParseError::TrailingInput)));
+}
@@ -0,0 +1,21 @@
+extern crate rhai;
+
+use rhai::Engine;
+
+#[test]
+fn test_sequence() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = 1; x += 1, x *= 10, x + 1"), Ok(21));
+    assert_eq!(engine.eval::<i64>("let x = 0; let z = x = 5, x * 2; z"), Ok(10));
+    assert_eq!(engine.eval::<i64>("fn f(a, b) { return a, b } f(1, 2)"), Ok(2));
+}
+
+#[test]
+fn test_sequence_leaves_lists_alone() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("fn add(a, b) { a + b } add(1, 2), add(3, 4)"), Ok(7));
+    assert_eq!(engine.eval::<i64>("let a = [1, 2, 3], 0; len([1, 2, 3])"), Ok(3));
+    assert_eq!(engine.eval::<i64>("let a = [4, 5]; a[1]"), Ok(5));
+}
@@ -0,0 +1,28 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult};
+
+#[test]
+fn test_strict_types() {
+    let mut engine = Engine::builder().strict_types().build();
+
+    assert_eq!(engine.eval::<String>("let x = 5; x = \"hi\"; x"),
+               Err(EvalAltResult::ErrorTypeMismatch("x holds integer, not string".to_string())));
+    assert_eq!(engine.eval::<i64>("let x = 5; x = 6; x += 1; x"), Ok(7));
+    assert_eq!(engine.eval::<String>("let x = 5; let x = \"hi\"; x"), Ok("hi".to_string()));
+    assert_eq!(engine.eval::<f64>("let x; x = 1.5; x"), Ok(1.5));
+    assert_eq!(engine.eval::<bool>("let x; x = 1.5; x = true"),
+               Err(EvalAltResult::ErrorTypeMismatch("x holds float, not boolean".to_string())));
+    assert_eq!(engine.eval::<bool>("let x = 5; x = x == 5; x"),
+               Err(EvalAltResult::ErrorTypeMismatch("x holds integer, not boolean".to_string())));
+    assert_eq!(engine.eval::<String>("let s = \"a\"; s += \"b\"; s = s + \"c\"; s"), Ok("abc".to_string()));
+    assert_eq!(engine.eval::<i64>("fn f(a) { a = \"no\"; 0 } f(1)"),
+               Err(EvalAltResult::ErrorTypeMismatch("a holds integer, not string".to_string())));
+}
+
+#[test]
+fn test_dynamic_types_by_default() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("let x = 5; x = \"hi\"; x"), Ok("hi".to_string()));
+}
@@ -1,20 +1,68 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{Engine, EvalAltResult};
 
 #[test]
 fn test_string() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<String>("\"Test string: \\u2764\"") {
-        assert_eq!(result, "Test string: ❤");
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval::<String>("\"foo\" + \"bar\"") {
-        assert_eq!(result, "foobar");
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<String>("\"Test string: \\u2764\""), Ok("Test string: ❤".to_string()));
+
+    assert_eq!(engine.eval::<String>("\"foo\" + \"bar\""), Ok("foobar".to_string()));
+}
+
+#[test]
+fn test_string_append() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("let s = \"ab\"; s += \"cd\"; s"), Ok("abcd".to_string()));
+    assert_eq!(engine.eval::<String>("let s = \"\"; let i = 0; while i < 3 { s += \"x\"; i += 1; } s"),
+               Ok("xxx".to_string()));
+    assert_eq!(engine.eval::<String>("let s = \"ab\"; s.push_str(\"cd\"); s"), Ok("abcd".to_string()));
+    assert_eq!(engine.eval::<i64>("let x = 1; x += 2; x"), Ok(3));
+}
+
+#[test]
+fn test_string_repeat() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<String>("repeat(\"ab\", 3)"), Ok("ababab".to_string()));
+    assert_eq!(engine.eval::<bool>("\"ab\" * 3 == \"ababab\""), Ok(true));
+    assert_eq!(engine.eval::<String>("let n = 2; \"-\" * (n + 1)"), Ok("---".to_string()));
+    assert_eq!(engine.eval::<String>("repeat(\"ab\", 0)"), Ok("".to_string()));
+    assert_eq!(engine.eval::<String>("\"ab\" * -2"), Ok("".to_string()));
+
+    assert_eq!(
+        engine.eval::<String>("\"ab\" * 9223372036854775807"),
+        Err(EvalAltResult::ErrorRuntime("Cannot repeat a string 9223372036854775807 times".into()))
+    );
+    assert_eq!(
+        engine.eval::<String>("\"ab\" * (1 << 40)"),
+        Err(EvalAltResult::ErrorRuntime("Cannot repeat a string 1099511627776 times".into()))
+    );
+
+    let mut engine = Engine::builder().max_string_size(4).build();
+
+    assert_eq!(engine.eval::<String>("repeat(\"ab\", 2)"), Ok("abab".to_string()));
+    assert_eq!(
+        engine.eval::<String>("repeat(\"ab\", 3)"),
+        Err(EvalAltResult::ErrorRuntime("Cannot repeat a string 3 times".into()))
+    );
+}
+
+#[test]
+fn test_string_search() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<bool>("starts_with(\"config.toml\", \"config\")"), Ok(true));
+    assert_eq!(engine.eval::<bool>("starts_with(\"config.toml\", \"toml\")"), Ok(false));
+    assert_eq!(engine.eval::<bool>("let s = \"config.toml\"; s.ends_with(\".toml\")"), Ok(true));
+    assert_eq!(engine.eval::<bool>("ends_with(\"a\", \"\")"), Ok(true));
+    assert_eq!(engine.eval::<bool>("contains(\"key = value\", \" = \")"), Ok(true));
+    assert_eq!(engine.eval::<bool>("contains(\"key\", \"value\")"), Ok(false));
+
+    assert_eq!(engine.eval::<i64>("index_of(\"key = value\", \"=\")"), Ok(4));
+    assert_eq!(engine.eval::<i64>("index_of(\"héllo\", \"l\")"), Ok(2));
+    assert_eq!(engine.eval::<i64>("index_of(\"hello\", \"z\")"), Ok(-1));
+    assert_eq!(engine.eval::<i64>("index_of(\"hello\", \"\")"), Ok(0));
 }
@@ -0,0 +1,39 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, ParseError};
+
+#[test]
+fn test_ternary() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = 5; let y = x > 3 ? 1 : 2; y"), Ok(1));
+    assert_eq!(engine.eval::<i64>("let x = 2; x > 3 ? 1 : 2"), Ok(2));
+    assert_eq!(engine.eval::<String>("let n = 1; n == 1 ? \"one\" : \"many\""), Ok("one".into()));
+    assert_eq!(engine.eval::<i64>("fn f(x) { x * 10 } f(true ? 1 + 1 : 3)"), Ok(20));
+    assert_eq!(engine.eval::<i64>("let a = [false ? 1 : 2, 3]; a[0]"), Ok(2));
+}
+
+#[test]
+fn test_ternary_nested() {
+    let mut engine = Engine::new();
+
+    let script = "fn sign(x) { x < 0 ? -1 : x == 0 ? 0 : 1 } sign(-5) * 100 + sign(0) * 10 + sign(7)";
+    assert_eq!(engine.eval::<i64>(script), Ok(-99));
+    assert_eq!(engine.eval::<i64>("true ? false ? 1 : 2 : 3"), Ok(2));
+}
+
+#[test]
+fn test_ternary_only_runs_one_branch() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let n = 0; true ? n += 1 : n += 10; n"), Ok(1));
+    assert_eq!(engine.eval::<i64>("let x = 0; false ? 1 / x : 4"), Ok(4));
+}
+
+#[test]
+fn test_ternary_errors() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("1 ? 2 : 3"), Err(EvalAltResult::ErrorIfGuardMismatch));
+    assert_eq!(engine.eval::<i64>("true ? 2 ; 3"), Err(EvalAltResult::ErrorParsing(ParseError::MissingColon)));
+}
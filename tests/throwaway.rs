@@ -0,0 +1,21 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, Position, RegisterFn};
+
+#[test]
+fn test_throwaway_let() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("side_effect", || 1i64);
+
+    assert_eq!(engine.eval::<i64>("let _ = side_effect(); let _ = 2; 3"), Ok(3));
+    assert_eq!(engine.eval::<i64>("let _ = 5; _"),
+               Err(EvalAltResult::ErrorVariableNotFound("_".to_string(), Position { line: 1, column: 12 })));
+}
+
+#[test]
+fn test_throwaway_params() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("fn second(_, x, _) { x } second(1, 2, 3)"), Ok(2));
+}
@@ -0,0 +1,60 @@
+extern crate rhai;
+
+use rhai::{tokenize, tokenize_at, LexError, Span, Token};
+
+#[test]
+fn test_tokenize() {
+    let tokens = tokenize("let x = foo(1, \"hi\");");
+
+    assert_eq!(tokens,
+               vec![(Token::Var, Span { start: 0, end: 3 }),
+                    (Token::Identifier("x".into()), Span { start: 4, end: 5 }),
+                    (Token::Equals, Span { start: 6, end: 7 }),
+                    (Token::Identifier("foo".into()), Span { start: 8, end: 11 }),
+                    (Token::LParen, Span { start: 11, end: 12 }),
+                    (Token::IntConst(1), Span { start: 12, end: 13 }),
+                    (Token::Comma, Span { start: 13, end: 14 }),
+                    (Token::StringConst("hi".into()), Span { start: 15, end: 19 }),
+                    (Token::RParen, Span { start: 19, end: 20 }),
+                    (Token::Semicolon, Span { start: 20, end: 21 })]);
+}
+
+#[test]
+fn test_tokenize_skips_comments() {
+    let input = "a /* note */ += 1 // done\n<= 'é'";
+    let tokens = tokenize(input);
+    let spans: Vec<&str> = tokens.iter().map(|&(_, span)| &input[span.start..span.end]).collect();
+
+    assert_eq!(spans, vec!["a", "+=", "1", "<=", "'é'"]);
+    assert_eq!(tokens[4].0, Token::CharConst('é'));
+}
+
+#[test]
+fn test_tokenize_keeps_going_after_errors() {
+    let tokens = tokenize("1 $ 2");
+
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(tokens[1].0, Token::LexErr(LexError::UnexpectedChar));
+    assert_eq!(tokens[2], (Token::IntConst(2), Span { start: 4, end: 5 }));
+}
+
+#[test]
+fn test_tokenize_raw_identifier() {
+    let tokens: Vec<Token> = tokenize("a.`type`").into_iter().map(|(t, _)| t).collect();
+    assert_eq!(tokens, vec![Token::Identifier("a".into()), Token::Period, Token::Identifier("type".into())]);
+
+    assert_eq!(tokenize("``")[0].0, Token::LexErr(LexError::MalformedIdentifier));
+    assert_eq!(tokenize("`type")[0].0, Token::LexErr(LexError::MalformedIdentifier));
+    assert_eq!(tokenize("`ty\npe`")[0].0, Token::LexErr(LexError::MalformedIdentifier));
+}
+
+#[test]
+fn test_tokenize_at_offset() {
+    let input = "let x = 1;\nlet y = \"é\" + x;";
+    let start = input.find('\n').unwrap() + 1;
+    let tokens = tokenize_at(&input[start..], start);
+
+    assert_eq!(tokens, tokenize(input).split_off(5));
+    assert_eq!(tokens[3], (Token::StringConst("é".into()), Span { start: 19, end: 23 }));
+    assert_eq!(&input[tokens[5].1.start..tokens[5].1.end], "x");
+}
@@ -0,0 +1,13 @@
+extern crate rhai;
+
+use rhai::Engine;
+
+#[test]
+fn test_top_level_return() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x = 5; return x + 1; x"), Ok(6));
+    assert_eq!(engine.eval::<i64>("let x = 5; if x > 1 { return 1; } 2"), Ok(1));
+    assert_eq!(engine.eval::<()>("return; 5"), Ok(()));
+    assert_eq!(engine.consume("return 1; undefined_fn()"), Ok(()));
+}
@@ -0,0 +1,54 @@
+extern crate rhai;
+
+use rhai::{Engine, EvalAltResult, ParseError, RegisterFn};
+
+#[test]
+fn test_typed_let() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x: integer = 40; x + 2"), Ok(42));
+    assert_eq!(engine.eval::<String>("let s: string = \"hi\"; s"), Ok("hi".to_string()));
+    assert_eq!(engine.eval::<bool>("let a: array = [1, 2]; let f: float = 1.5; let b: boolean = true; b"), Ok(true));
+
+    assert_eq!(engine.eval::<i64>("let x: integer = \"5\"; 1"),
+               Err(EvalAltResult::ErrorTypeMismatch("x is declared integer, not string".to_string())));
+
+    // Rust's names for the builtin types work too
+    assert_eq!(engine.eval::<i64>("let x: int = 5; let y: i64 = 6; x + y"), Ok(11));
+    assert_eq!(engine.eval::<bool>("let x: f64 = 0.5; let b: bool = x < 1.0; b"), Ok(true));
+    assert_eq!(engine.eval::<i64>("let x: int = \"5\"; 1"),
+               Err(EvalAltResult::ErrorTypeMismatch("x is declared integer, not string".to_string())));
+
+    assert_eq!(engine.eval::<i64>("let x: integr = 5; 1"),
+               Err(EvalAltResult::ErrorTypeMismatch("x is declared integr, which isn't a known type".to_string())));
+
+    // The type only checks the first value
+    assert_eq!(engine.eval::<String>("let x: integer = 5; x = \"five\"; x"), Ok("five".to_string()));
+}
+
+#[test]
+fn test_typed_let_host_type() {
+    #[derive(Clone)]
+    struct Player {
+        hp: i64,
+    }
+
+    let mut engine = Engine::new();
+
+    engine.register_type_name::<Player>("Player");
+    engine.register_fn("host_player", || Player { hp: 10 });
+    engine.register_fn("host_hp", |p: &mut Player| p.hp);
+
+    assert_eq!(engine.eval::<i64>("let p: Player = host_player(); host_hp(p)"), Ok(10));
+    assert_eq!(engine.eval::<i64>("let p: Player = 10; 1"),
+               Err(EvalAltResult::ErrorTypeMismatch("p is declared Player, not integer".to_string())));
+}
+
+#[test]
+fn test_typed_let_syntax_errors() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<i64>("let x: = 5; x"), Err(EvalAltResult::ErrorParsing(ParseError::VarMissingType)));
+    assert_eq!(engine.eval::<i64>("let x: integer; 1"),
+               Err(EvalAltResult::ErrorParsing(ParseError::VarTypeWithoutValue)));
+}
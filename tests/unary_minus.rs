@@ -1,6 +1,6 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{Engine, EvalAltResult, RegisterFn};
 
 #[test]
 fn test_unary_minus() {
@@ -12,3 +12,38 @@ fn test_unary_minus() {
 
 	assert_eq!(engine.eval::<i64>("5 - -(-5)").unwrap(), 0);
 }
+
+#[test]
+fn test_unary_minus_unsigned() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("unsigned", |x: i64| x as u32);
+
+    assert_eq!(engine.eval::<u32>("let x = -unsigned(0); x"), Ok(0));
+    assert_eq!(engine.eval::<u32>("let x = -unsigned(5); x"),
+               Err(EvalAltResult::ErrorArithmetic("Cannot negate unsigned value".to_string())));
+}
+
+#[test]
+fn test_negative_floats() {
+    let mut engine = Engine::new();
+
+    engine.register_fn("half", |x: f64| x / 2.0);
+
+    assert_eq!(engine.eval::<f64>("-3.25"), Ok(-3.25));
+    assert_eq!(engine.eval::<f64>("-.5"), Ok(-0.5));
+    assert_eq!(engine.eval::<f64>(".25 + 1.0"), Ok(1.25));
+    assert_eq!(engine.eval::<f64>("let a = [-1.0, -2.5, -.5]; a[1] + a[2]"), Ok(-3.0));
+    assert_eq!(engine.eval::<f64>("half(-0.5)"), Ok(-0.25));
+    assert_eq!(engine.eval::<f64>("half(-.5) * -2.0"), Ok(0.5));
+    assert_eq!(engine.eval::<f64>("1.0 - -0.5"), Ok(1.5));
+    assert_eq!(engine.eval::<f64>("- -1.5"), Ok(1.5));
+
+    // The sign belongs to the number, so it's applied before any operator
+    assert_eq!(engine.eval::<f64>("-1.5 * 2.0"), Ok(-3.0));
+    assert_eq!(engine.eval::<f64>("-2.0 ~ 2"), Ok(4.0));
+    assert_eq!(engine.eval::<f64>("-(2.0 ~ 2)"), Ok(-4.0));
+
+    // `..` right after a number is a range, not a decimal point
+    assert_eq!(engine.eval::<i64>("let n = 0; for i in -2..0 { n += 1; } n"), Ok(2));
+}
@@ -1,36 +1,52 @@
 extern crate rhai;
 
-use rhai::Engine;
+use rhai::{Engine, EvalAltResult, Position};
 
 #[test]
 fn test_unit() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<()>("let x = (); x") {
-        assert_eq!(result, ());
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<()>("let x = (); x"), Ok(()));
 }
 
 #[test]
 fn test_unit_eq() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<bool>("let x = (); let y = (); x == y") {
-        assert!(result);
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<bool>("let x = (); let y = (); x == y"), Ok(true));
 }
 
 #[test]
 fn test_unit_with_spaces() {
     let mut engine = Engine::new();
 
-    if let Ok(result) = engine.eval::<()>("let x = ( ); x") {
-        assert_eq!(result, ());
-    } else {
-        assert!(false);
-    }
+    assert_eq!(engine.eval::<()>("let x = ( ); x"), Ok(()));
+}
+
+#[test]
+fn test_unit_compare_any_type() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<bool>("let x = (); x == ()"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let x = (); x != ()"), Ok(false));
+    assert_eq!(engine.eval::<bool>("let x = 5; x == ()"), Ok(false));
+    assert_eq!(engine.eval::<bool>("let x = \"a\"; () != x"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let x = { if false { 1 } }; x == ()"), Ok(true));
+
+    // Other values of different types still can't be compared
+    assert_eq!(
+        engine.eval::<bool>("1 == \"a\""),
+        Err(EvalAltResult::ErrorFunctionNotFound("== (integer,string)".into(), Position { line: 1, column: 3 }))
+    );
+}
+
+#[test]
+fn test_is_unit() {
+    let mut engine = Engine::new();
+
+    assert_eq!(engine.eval::<bool>("is_unit(())"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let x = { let a = 1; }; is_unit(x)"), Ok(true));
+    assert_eq!(engine.eval::<bool>("let x; is_unit(x)"), Ok(true));
+    assert_eq!(engine.eval::<bool>("is_unit(0)"), Ok(false));
+    assert_eq!(engine.eval::<bool>("is_unit([])"), Ok(false));
 }
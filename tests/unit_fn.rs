@@ -0,0 +1,38 @@
+extern crate rhai;
+
+use rhai::{Engine, RegisterFn};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+#[test]
+fn test_unit_returning_fn() {
+    let mut engine = Engine::new();
+    let flag = Rc::new(Cell::new(false));
+
+    let f = flag.clone();
+    engine.register_fn("set_flag", move |b: bool| f.set(b));
+
+    assert_eq!(engine.eval::<()>("set_flag(true)"), Ok(()));
+    assert!(flag.get());
+
+    assert_eq!(engine.eval::<i64>("set_flag(false); 42"), Ok(42));
+    assert!(!flag.get());
+
+    assert_eq!(engine.eval::<bool>("let x = set_flag(true); x == ()"), Ok(true));
+    assert_eq!(engine.consume("set_flag(false)"), Ok(()));
+    assert!(!flag.get());
+}
+
+#[test]
+fn test_unit_returning_fn_in_expressions() {
+    let mut engine = Engine::new();
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let l = log.clone();
+    engine.register_fn("log", move |msg: String| l.borrow_mut().push(msg));
+
+    assert_eq!(engine.eval::<i64>("fn twice(x) { log(\"twice\"); x * 2 } if true { log(\"if\") } twice(4)"),
+               Ok(8));
+    assert_eq!(engine.eval::<bool>("log(\"block\") == ()"), Ok(true));
+    assert_eq!(*log.borrow(), vec!["if", "twice", "block"]);
+}
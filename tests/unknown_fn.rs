@@ -0,0 +1,34 @@
+extern crate rhai;
+
+use rhai::{Any, Engine, EvalAltResult, Position};
+
+#[test]
+fn test_unknown_fn_resolver() {
+    let mut engine = Engine::new();
+
+    engine.on_unknown_fn(|name, args| {
+        if !name.starts_with("host_") {
+            return None;
+        }
+
+        // Proxy `host_<op>` to the sum of the integer arguments
+        let total: i64 = args.iter().filter_map(|x| x.downcast_ref::<i64>()).sum();
+        Some(Ok(Box::new(total) as Box<dyn Any>))
+    });
+
+    assert_eq!(engine.eval::<i64>("host_sum(1, 2, 3)"), Ok(6));
+    assert_eq!(engine.eval::<i64>("let x = 4; host_double(x, x)"), Ok(8));
+    assert_eq!(engine.eval::<i64>("1 + 2"), Ok(3));
+    assert_eq!(engine.eval::<i64>("other(1)"),
+               Err(EvalAltResult::ErrorFunctionNotFound("other (integer)".into(), Position { line: 1, column: 1 })));
+}
+
+#[test]
+fn test_unknown_fn_resolver_error() {
+    let mut engine = Engine::new();
+
+    engine.on_unknown_fn(|name, _| Some(Err(EvalAltResult::ErrorRuntime(format!("{} is disabled", name)))));
+
+    assert_eq!(engine.eval::<i64>("launch()"),
+               Err(EvalAltResult::ErrorRuntime("launch is disabled".into())));
+}
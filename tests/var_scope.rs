@@ -1,42 +1,128 @@
 extern crate rhai;
 
-use rhai::{Engine, Scope};
+use rhai::{Engine, EvalAltResult, Position, Scope, ScopeExt};
 
 #[test]
 fn test_var_scope() {
     let mut engine = Engine::new();
     let mut scope: Scope = Vec::new();
 
-    if let Ok(_) = engine.eval_with_scope::<()>(&mut scope, "let x = 4 + 5") {
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval_with_scope::<i64>(&mut scope, "x") {
-        assert_eq!(result, 9);
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(_) = engine.eval_with_scope::<()>(&mut scope, "x = x + 1; x = x + 2;") {
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval_with_scope::<i64>(&mut scope, "x") {
-        assert_eq!(result, 12);
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(_) = engine.eval_with_scope::<()>(&mut scope, "{let x = 3}") {
-    } else {
-        assert!(false);
-    }
-
-    if let Ok(result) = engine.eval_with_scope::<i64>(&mut scope, "x") {
-        assert_eq!(result, 12);
-    } else {
-        assert!(false);
-    }
+    assert!(engine.eval_with_scope::<()>(&mut scope, "let x = 4 + 5").is_ok());
+
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "x"), Ok(9));
+
+    assert!(engine.eval_with_scope::<()>(&mut scope, "x = x + 1; x = x + 2;").is_ok());
+
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "x"), Ok(12));
+
+    assert!(engine.eval_with_scope::<()>(&mut scope, "{let x = 3}").is_ok());
+
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "x"), Ok(12));
+}
+
+#[test]
+fn test_scope_frames() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    assert!(engine.eval_with_scope::<()>(&mut scope, "let x = 1").is_ok());
+
+    let frame = scope.push_frame();
+    assert!(engine.eval_with_scope::<()>(&mut scope, "let y = x + 1; x = 10").is_ok());
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "x + y"), Ok(12));
+    scope.pop_frame(frame);
+
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "x"), Ok(10));
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "y"),
+               Err(EvalAltResult::ErrorVariableNotFound("y".to_string(), Position { line: 1, column: 1 })));
+}
+
+#[test]
+fn test_scope_push_value() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    scope.push_value("x", 40i64);
+    scope.push_value("name", "Bob".to_string());
+    scope.push_value("x", 2i64);
+
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "x"), Ok(2));
+    assert_eq!(engine.eval_with_scope::<String>(&mut scope, "name + \"!\""), Ok("Bob!".to_string()));
+}
+
+#[test]
+fn test_scope_push_value_with_push() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    // Both ways of seeding a variable give the same binding
+    scope.push(("x".to_string(), Box::new(1i64)));
+    scope.push_value("y", 2i64);
+
+    assert_eq!(scope.len(), 2);
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "x + y"), Ok(3));
+    assert_eq!(engine.eval_with_scope::<()>(&mut scope, "y = x"), Ok(()));
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "y"), Ok(1));
+}
+
+#[test]
+fn test_scope_after_error() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    assert!(engine.eval_with_scope::<()>(&mut scope, "let a = 1; { let b = 2; nope(); } let c = 3;").is_err());
+
+    assert_eq!(scope.len(), 1);
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "a"), Ok(1));
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "b"),
+               Err(EvalAltResult::ErrorVariableNotFound("b".into(), Position { line: 1, column: 1 })));
+
+    assert!(engine.consume_with_scope(&mut scope, "let d = 4; while true { let e = 5; nope(); }").is_err());
+    assert_eq!(scope.len(), 2);
+    assert_eq!(engine.eval_with_scope::<i64>(&mut scope, "a + d"), Ok(5));
+}
+
+#[test]
+fn test_scope_merge() {
+    let mut engine = Engine::new();
+
+    let mut base = Scope::new();
+    engine.eval_with_scope::<()>(&mut base, "let width = 80; let height = 24;").unwrap();
+
+    let mut overrides = Scope::new();
+    engine.eval_with_scope::<()>(&mut overrides, "let width = 120; let title = \"main\";").unwrap();
+
+    base.merge(overrides);
+
+    assert_eq!(engine.eval_with_scope::<i64>(&mut base, "width * height"), Ok(2880));
+    assert_eq!(engine.eval_with_scope::<String>(&mut base, "title"), Ok("main".to_string()));
+
+    // Assigning goes to the binding that won
+    assert_eq!(engine.eval_with_scope::<i64>(&mut base, "width = 100; width"), Ok(100));
+    assert_eq!(base.iter().filter(|&(name, _)| name == "width").count(), 2);
+}
+
+#[test]
+fn test_scope_get_set_value() {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    scope.set_value("volume", 5i64);
+    engine.eval_with_scope::<()>(&mut scope, "volume += 1; let name = \"main\";").unwrap();
+
+    assert_eq!(scope.get_value::<i64>("volume"), Some(6));
+    assert_eq!(scope.get_value::<String>("name"), Some("main".to_string()));
+    assert_eq!(scope.get_value::<bool>("volume"), None);
+    assert_eq!(scope.get_value::<i64>("nope"), None);
+
+    // Setting a variable again replaces it, even with another type
+    scope.set_value("volume", 1.5f64);
+    assert_eq!(scope.len(), 2);
+    assert_eq!(engine.eval_with_scope::<f64>(&mut scope, "volume * 2.0"), Ok(3.0));
+
+    // Only the most recent of two variables with the same name is seen or changed
+    scope.push_value("volume", 0i64);
+    scope.set_value("volume", 9i64);
+    assert_eq!(scope.get_value::<i64>("volume"), Some(9));
+    assert_eq!(scope[0].1.downcast_ref::<f64>(), Some(&1.5));
 }